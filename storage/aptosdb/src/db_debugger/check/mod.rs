@@ -0,0 +1,252 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::{
+        common::{
+            get_current_version_in_ledger_db, get_current_version_in_state_merkle_db,
+            get_latest_root_version_in_state_merkle_db, DbDir, OutputFormat,
+        },
+        ledger::{
+            check_accumulator::find_accumulator_mismatch,
+            check_kv_state_values::find_inconsistent_state_values,
+            check_version_data::{find_version_data_gaps, get_oldest_version_data_version},
+            orphaned_ledger_infos::{
+                find_epoch_endings_without_ledger_info, find_orphaned_ledger_infos,
+            },
+        },
+        state_tree::check_usage::get_state_items_and_leaf_count,
+    },
+    state_merkle_db::StateMerkleDb,
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::DB;
+use aptos_types::transaction::Version;
+use clap::Parser;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[clap(about = "Run all read-only consistency checks and report every anomaly found.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    #[clap(long, arg_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+#[derive(Serialize)]
+struct Anomaly {
+    schema: &'static str,
+    version: Option<Version>,
+    description: String,
+}
+
+#[derive(Serialize)]
+struct CorruptionReport {
+    ledger_db_version: Option<Version>,
+    state_merkle_db_version: Option<Version>,
+    anomalies: Vec<Anomaly>,
+}
+
+impl CorruptionReport {
+    fn add(&mut self, schema: &'static str, version: Option<Version>, description: String) {
+        self.anomalies.push(Anomaly {
+            schema,
+            version,
+            description,
+        });
+    }
+
+    fn print_text(&self) {
+        println!("Ledger db version: {:?}", self.ledger_db_version);
+        println!(
+            "State merkle db version: {:?}",
+            self.state_merkle_db_version
+        );
+        println!();
+        for anomaly in &self.anomalies {
+            match anomaly.version {
+                Some(version) => println!(
+                    "{:<30} version {:<20} {}",
+                    anomaly.schema, version, anomaly.description
+                ),
+                None => println!("{:<30} {}", anomaly.schema, anomaly.description),
+            }
+        }
+        println!("\nFound {} anomalies.", self.anomalies.len());
+    }
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let ledger_db = self.db_dir.open_ledger_db()?;
+        let state_merkle_db = StateMerkleDb::new(Arc::new(self.db_dir.open_state_merkle_db()?), 0);
+        let kv_db = if self.db_dir.has_kv_db() {
+            Some(self.db_dir.open_kv_db()?)
+        } else {
+            None
+        };
+
+        let mut report = CorruptionReport {
+            ledger_db_version: get_current_version_in_ledger_db(&ledger_db)?,
+            state_merkle_db_version: get_current_version_in_state_merkle_db(&state_merkle_db)?,
+            anomalies: Vec::new(),
+        };
+        if let Some(ledger_db_version) = report.ledger_db_version {
+            Self::check_version_data(&ledger_db, ledger_db_version, &mut report)?;
+            Self::check_accumulator(&ledger_db, ledger_db_version, &mut report)?;
+        }
+        Self::check_state_root(&ledger_db, &state_merkle_db, &mut report)?;
+        Self::check_state_values(&ledger_db, kv_db.as_ref(), &mut report)?;
+        Self::check_epoch_endings(&ledger_db, &mut report)?;
+
+        match self.format {
+            OutputFormat::Text => report.print_text(),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+        ensure!(
+            report.anomalies.is_empty(),
+            "Found {} anomalies.",
+            report.anomalies.len()
+        );
+
+        Ok(())
+    }
+
+    fn check_version_data(
+        ledger_db: &DB,
+        ledger_db_version: Version,
+        report: &mut CorruptionReport,
+    ) -> Result<()> {
+        // The pruner deletes the entries of pruned versions, so start from the oldest one.
+        let start_version = get_oldest_version_data_version(ledger_db)?.unwrap_or(0);
+        for (start, end) in find_version_data_gaps(ledger_db, start_version, ledger_db_version)? {
+            report.add(
+                "VersionDataSchema",
+                Some(start),
+                format!("missing versions [{}, {})", start, end),
+            );
+        }
+        Ok(())
+    }
+
+    fn check_accumulator(
+        ledger_db: &DB,
+        ledger_db_version: Version,
+        report: &mut CorruptionReport,
+    ) -> Result<()> {
+        if let Some(mismatch) = find_accumulator_mismatch(ledger_db, ledger_db_version + 1)? {
+            report.add(
+                "TransactionAccumulatorSchema",
+                Some(mismatch.diverged_at_version),
+                format!(
+                    "{} nodes found, first divergence at postorder index {}",
+                    mismatch.num_nodes, mismatch.first_divergence
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    fn check_state_root(
+        ledger_db: &DB,
+        state_merkle_db: &StateMerkleDb,
+        report: &mut CorruptionReport,
+    ) -> Result<()> {
+        let root_version = get_latest_root_version_in_state_merkle_db(state_merkle_db)?;
+        match (root_version, report.state_merkle_db_version) {
+            (None, Some(_)) => report.add(
+                "JellyfishMerkleNodeSchema",
+                None,
+                "nodes found but no state root".to_string(),
+            ),
+            (Some(root_version), Some(state_merkle_db_version))
+                if root_version < state_merkle_db_version =>
+            {
+                report.add(
+                    "JellyfishMerkleNodeSchema",
+                    Some(root_version + 1),
+                    format!(
+                        "nodes found up to version {} above the latest root at version {}",
+                        state_merkle_db_version, root_version
+                    ),
+                )
+            },
+            _ => (),
+        }
+        if let (Some(root_version), Some(ledger_db_version)) =
+            (root_version, report.ledger_db_version)
+        {
+            if root_version > ledger_db_version {
+                report.add(
+                    "JellyfishMerkleNodeSchema",
+                    Some(root_version),
+                    format!(
+                        "state root is ahead of the ledger db version {}",
+                        ledger_db_version
+                    ),
+                );
+            }
+        }
+        if let Some(root_version) = root_version {
+            let (state_items, leaf_count) =
+                get_state_items_and_leaf_count(ledger_db, state_merkle_db, root_version)?;
+            if state_items != Some(leaf_count) {
+                report.add(
+                    "VersionDataSchema",
+                    Some(root_version),
+                    format!(
+                        "state items {:?} don't match the {} leaves in the state tree",
+                        state_items, leaf_count
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn check_state_values(
+        ledger_db: &DB,
+        kv_db: Option<&DB>,
+        report: &mut CorruptionReport,
+    ) -> Result<()> {
+        find_inconsistent_state_values(ledger_db, kv_db, 0, Version::MAX, |index, in_both| {
+            report.add(
+                "StateValueSchema",
+                Some(index.version),
+                format!(
+                    "value stale since {} is {}, state key: {:?}",
+                    index.stale_since_version,
+                    if in_both {
+                        "in both the ledger db and the K/V db"
+                    } else {
+                        "missing"
+                    },
+                    index.state_key
+                ),
+            )
+        })?;
+        Ok(())
+    }
+
+    fn check_epoch_endings(ledger_db: &DB, report: &mut CorruptionReport) -> Result<()> {
+        for (epoch, version) in find_orphaned_ledger_infos(ledger_db, 0, Version::MAX)? {
+            report.add(
+                "LedgerInfoSchema",
+                Some(version),
+                format!("LedgerInfo of epoch {} has no EpochByVersion entry", epoch),
+            );
+        }
+        for (version, epoch) in find_epoch_endings_without_ledger_info(ledger_db, 0, Version::MAX)?
+        {
+            report.add(
+                "EpochByVersionSchema",
+                Some(version),
+                format!("epoch {} has no LedgerInfo", epoch),
+            );
+        }
+        Ok(())
+    }
+}
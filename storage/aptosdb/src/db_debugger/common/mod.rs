@@ -2,16 +2,44 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    db_options::{ledger_db_column_families, state_merkle_db_column_families},
-    LEDGER_DB_NAME, STATE_MERKLE_DB_NAME,
+    db_options::{
+        gen_kv_cfds, gen_ledger_cfds, gen_state_merkle_cfds, kv_db_column_families,
+        ledger_db_column_families, state_merkle_db_column_families,
+    },
+    schema::{
+        db_metadata::{DbMetadataKey, DbMetadataSchema},
+        epoch_by_version::EpochByVersionSchema,
+        jellyfish_merkle_node::JellyfishMerkleNodeSchema,
+        transaction_info::TransactionInfoSchema,
+    },
+    KV_DB_NAME, LEDGER_DB_NAME, STATE_MERKLE_DB_NAME,
 };
-use anyhow::Result;
-use aptos_types::nibble::{nibble_path::NibblePath, Nibble};
-use clap::Parser;
+use anyhow::{bail, ensure, Result};
+use aptos_config::config::RocksdbConfigs;
+use aptos_jellyfish_merkle::node_type::NodeKey;
+use aptos_rocksdb_options::gen_rocksdb_options;
+use aptos_schemadb::{ReadOptions, DB};
+use aptos_types::{
+    nibble::{nibble_path::NibblePath, Nibble},
+    transaction::Version,
+};
+use clap::{ArgEnum, Parser};
 use std::path::{Path, PathBuf};
 
 pub const PAGE_SIZE: usize = 10;
 
+pub const DELETE_BATCH_SIZE: usize = 10_000;
+
+/// Sweeps that skip errors give up after this many errors in a row, since an iterator that fails
+/// at the RocksDB level doesn't move past the failure.
+const MAX_CONSECUTIVE_ERRORS: usize = 1_000;
+
+#[derive(ArgEnum, Clone, Copy, Debug)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
 #[derive(Parser)]
 pub struct DbDir {
     #[clap(long, parse(from_os_str))]
@@ -22,7 +50,7 @@ impl DbDir {
     pub fn open_state_merkle_db(&self) -> Result<aptos_schemadb::DB> {
         aptos_schemadb::DB::open_cf_readonly(
             &aptos_schemadb::Options::default(),
-            self.db_dir.join(STATE_MERKLE_DB_NAME).as_path(),
+            self.db_path(STATE_MERKLE_DB_NAME)?.as_path(),
             STATE_MERKLE_DB_NAME,
             state_merkle_db_column_families(),
         )
@@ -31,11 +59,94 @@ impl DbDir {
     pub fn open_ledger_db(&self) -> Result<aptos_schemadb::DB> {
         aptos_schemadb::DB::open_cf_readonly(
             &aptos_schemadb::Options::default(),
-            self.db_dir.join(LEDGER_DB_NAME).as_path(),
+            self.db_path(LEDGER_DB_NAME)?.as_path(),
             LEDGER_DB_NAME,
             ledger_db_column_families(),
         )
     }
+
+    pub fn open_kv_db(&self) -> Result<aptos_schemadb::DB> {
+        aptos_schemadb::DB::open_cf_readonly(
+            &aptos_schemadb::Options::default(),
+            self.db_path(KV_DB_NAME)?.as_path(),
+            KV_DB_NAME,
+            kv_db_column_families(),
+        )
+    }
+
+    pub fn has_kv_db(&self) -> bool {
+        self.has_db(KV_DB_NAME)
+    }
+
+    /// Returns whether `db_dir` contains a db named `name` that can be opened.
+    pub fn has_db(&self, name: &str) -> bool {
+        self.db_path(name).is_ok()
+    }
+
+    /// Opens the state merkle db for writing. The node must not be running on the same db.
+    pub fn open_state_merkle_db_for_write(&self) -> Result<aptos_schemadb::DB> {
+        let rocksdb_config = RocksdbConfigs::default().state_merkle_db_config;
+        aptos_schemadb::DB::open_cf(
+            &gen_rocksdb_options(&rocksdb_config, false),
+            self.db_path(STATE_MERKLE_DB_NAME)?.as_path(),
+            STATE_MERKLE_DB_NAME,
+            gen_state_merkle_cfds(&rocksdb_config),
+        )
+    }
+
+    /// Opens the ledger db for writing. The node must not be running on the same db.
+    pub fn open_ledger_db_for_write(&self) -> Result<aptos_schemadb::DB> {
+        let rocksdb_config = RocksdbConfigs::default().ledger_db_config;
+        aptos_schemadb::DB::open_cf(
+            &gen_rocksdb_options(&rocksdb_config, false),
+            self.db_path(LEDGER_DB_NAME)?.as_path(),
+            LEDGER_DB_NAME,
+            gen_ledger_cfds(&rocksdb_config),
+        )
+    }
+
+    /// Opens the K/V db for writing. The node must not be running on the same db.
+    pub fn open_kv_db_for_write(&self) -> Result<aptos_schemadb::DB> {
+        let rocksdb_config = RocksdbConfigs::default().kv_db_config;
+        aptos_schemadb::DB::open_cf(
+            &gen_rocksdb_options(&rocksdb_config, false),
+            self.db_path(KV_DB_NAME)?.as_path(),
+            KV_DB_NAME,
+            gen_kv_cfds(&rocksdb_config),
+        )
+    }
+
+    /// Checks `db_dir` contains a ledger db and a state merkle db.
+    pub fn validate(&self) -> Result<()> {
+        self.db_path(LEDGER_DB_NAME)?;
+        self.db_path(STATE_MERKLE_DB_NAME)?;
+        Ok(())
+    }
+
+    /// Returns the path of the db named `name`, failing with a clear error if it doesn't look like
+    /// a db, rather than letting RocksDB fail to open it (or create an empty db when writing).
+    fn db_path(&self, name: &str) -> Result<PathBuf> {
+        ensure!(
+            self.db_dir.is_dir(),
+            "db_dir {:?} doesn't exist or is not a directory.",
+            self.db_dir,
+        );
+        let path = self.db_dir.join(name);
+        ensure!(
+            path.join("CURRENT").is_file(),
+            "{:?} doesn't look like an Aptos db directory: no {} found at {:?}.",
+            self.db_dir,
+            name,
+            path,
+        );
+        Ok(path)
+    }
+}
+
+impl From<PathBuf> for DbDir {
+    fn from(db_dir: PathBuf) -> Self {
+        Self { db_dir }
+    }
 }
 
 impl AsRef<Path> for DbDir {
@@ -49,3 +160,132 @@ pub fn parse_nibble_path(src: &str) -> Result<NibblePath> {
         .map(|c| Ok(Nibble::from(u8::from_str_radix(&c.to_string(), 16)?)))
         .collect()
 }
+
+/// Returns the version of the latest `TransactionInfo` in the ledger db, or `None` if the ledger
+/// db is empty.
+pub fn get_current_version_in_ledger_db(ledger_db: &DB) -> Result<Option<Version>> {
+    let mut iter = ledger_db.iter::<TransactionInfoSchema>(ReadOptions::default())?;
+    iter.seek_to_last();
+    Ok(iter.next().transpose()?.map(|(version, _)| version))
+}
+
+/// Returns the oldest version not yet pruned by the ledger pruner, which is 0 if the pruner never
+/// ran.
+pub fn get_ledger_pruner_progress(ledger_db: &DB) -> Result<Version> {
+    Ok(ledger_db
+        .get::<DbMetadataSchema>(&DbMetadataKey::LedgerPrunerProgress)?
+        .map_or(0, |v| v.expect_version()))
+}
+
+/// Returns the version of the latest node in the state merkle db, or `None` if the state merkle
+/// db is empty.
+pub fn get_current_version_in_state_merkle_db(state_merkle_db: &DB) -> Result<Option<Version>> {
+    let mut iter = state_merkle_db.iter::<JellyfishMerkleNodeSchema>(ReadOptions::default())?;
+    iter.seek_to_last();
+    Ok(iter
+        .next()
+        .transpose()?
+        .map(|(node_key, _)| node_key.version()))
+}
+
+/// Returns the version of the latest root node in the state merkle db, or `None` if there is no
+/// root. Unlike `get_current_version_in_state_merkle_db`, this skips nodes of partially written
+/// versions.
+pub fn get_latest_root_version_in_state_merkle_db(state_merkle_db: &DB) -> Result<Option<Version>> {
+    let mut iter = state_merkle_db.rev_iter::<JellyfishMerkleNodeSchema>(ReadOptions::default())?;
+    iter.seek_to_last();
+    while let Some((node_key, _)) = iter.next().transpose()? {
+        if node_key.nibble_path().num_nibbles() == 0 {
+            return Ok(Some(node_key.version()));
+        }
+        // The root of a version is its smallest key, jump to the root of this version.
+        iter.seek_for_prev(&NodeKey::new_empty_path(node_key.version()))?;
+    }
+    Ok(None)
+}
+
+/// Returns whether the state merkle db has a state root at `version`.
+pub fn root_exists_at_version(state_merkle_db: &DB, version: Version) -> Result<bool> {
+    Ok(state_merkle_db
+        .get::<JellyfishMerkleNodeSchema>(&NodeKey::new_empty_path(version))?
+        .is_some())
+}
+
+/// Returns `(version, epoch)` of every epoch ending in `[start_version, end_version)`.
+pub fn get_epoch_endings_in_range(
+    ledger_db: &DB,
+    start_version: Version,
+    end_version: Version,
+) -> Result<Vec<(Version, u64)>> {
+    let mut iter = ledger_db.iter::<EpochByVersionSchema>(ReadOptions::default())?;
+    iter.seek(&start_version)?;
+    let mut ret = Vec::new();
+    for item in iter {
+        let (version, epoch) = item?;
+        if version >= end_version {
+            break;
+        }
+        ret.push((version, epoch));
+    }
+    Ok(ret)
+}
+
+/// Returns `(version, epoch)` of the latest epoch ending at or before `version`.
+pub fn get_epoch_ending_at_or_before(
+    ledger_db: &DB,
+    version: Version,
+) -> Result<Option<(Version, u64)>> {
+    let mut iter = ledger_db.iter::<EpochByVersionSchema>(ReadOptions::default())?;
+    iter.seek_for_prev(&version)?;
+    iter.next().transpose()
+}
+
+/// Decides what to do with the rows of a sweep that fail to be read.
+pub struct ErrorSkipper {
+    continue_on_error: bool,
+    num_skipped: usize,
+    num_consecutive: usize,
+}
+
+impl ErrorSkipper {
+    pub fn new(continue_on_error: bool) -> Self {
+        Self {
+            continue_on_error,
+            num_skipped: 0,
+            num_consecutive: 0,
+        }
+    }
+
+    /// Returns the row if it was read successfully. Otherwise the error is propagated, unless
+    /// `continue_on_error` is set, in which case it is logged and `None` is returned.
+    pub fn check<T>(&mut self, row: Result<T>) -> Result<Option<T>> {
+        match row {
+            Ok(row) => {
+                self.num_consecutive = 0;
+                Ok(Some(row))
+            },
+            Err(err) if self.continue_on_error => {
+                if self.num_skipped < PAGE_SIZE {
+                    eprintln!("Skipping row: {:?}", err);
+                }
+                self.num_skipped += 1;
+                self.num_consecutive += 1;
+                if self.num_consecutive >= MAX_CONSECUTIVE_ERRORS {
+                    bail!(
+                        "Giving up after {} consecutive errors.",
+                        self.num_consecutive
+                    );
+                }
+                Ok(None)
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn num_skipped(&self) -> usize {
+        self.num_skipped
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test;
@@ -0,0 +1,72 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{get_current_version_in_state_merkle_db, get_latest_root_version_in_state_merkle_db};
+use crate::{
+    db_options::gen_state_merkle_cfds, schema::jellyfish_merkle_node::JellyfishMerkleNodeSchema,
+    STATE_MERKLE_DB_NAME,
+};
+use aptos_config::config::RocksdbConfigs;
+use aptos_jellyfish_merkle::node_type::{Node, NodeKey};
+use aptos_rocksdb_options::gen_rocksdb_options;
+use aptos_schemadb::{SchemaBatch, DB};
+use aptos_temppath::TempPath;
+use aptos_types::{nibble::Nibble, transaction::Version};
+
+pub(crate) fn open_state_merkle_db(tmp_dir: &TempPath) -> DB {
+    let rocksdb_config = RocksdbConfigs::default().state_merkle_db_config;
+    DB::open_cf(
+        &gen_rocksdb_options(&rocksdb_config, false),
+        tmp_dir.path().join(STATE_MERKLE_DB_NAME),
+        STATE_MERKLE_DB_NAME,
+        gen_state_merkle_cfds(&rocksdb_config),
+    )
+    .unwrap()
+}
+
+/// Writes a node under the root of `version`, and the root itself if `with_root` is set.
+pub(crate) fn put_nodes(state_merkle_db: &DB, version: Version, with_root: bool) {
+    let batch = SchemaBatch::new();
+    let root_key = NodeKey::new_empty_path(version);
+    batch
+        .put::<JellyfishMerkleNodeSchema>(
+            &root_key.gen_child_node_key(version, Nibble::from(1)),
+            &Node::Null,
+        )
+        .unwrap();
+    if with_root {
+        batch
+            .put::<JellyfishMerkleNodeSchema>(&root_key, &Node::Null)
+            .unwrap();
+    }
+    state_merkle_db.write_schemas(batch).unwrap();
+}
+
+#[test]
+fn test_get_latest_root_version_in_state_merkle_db() {
+    let tmp_dir = TempPath::new();
+    let state_merkle_db = open_state_merkle_db(&tmp_dir);
+    assert_eq!(
+        get_latest_root_version_in_state_merkle_db(&state_merkle_db).unwrap(),
+        None
+    );
+
+    put_nodes(&state_merkle_db, 0, true);
+    put_nodes(&state_merkle_db, 2, true);
+    assert_eq!(
+        get_latest_root_version_in_state_merkle_db(&state_merkle_db).unwrap(),
+        Some(2)
+    );
+
+    // Partially written versions without a root are skipped.
+    put_nodes(&state_merkle_db, 3, false);
+    put_nodes(&state_merkle_db, 4, false);
+    assert_eq!(
+        get_current_version_in_state_merkle_db(&state_merkle_db).unwrap(),
+        Some(4)
+    );
+    assert_eq!(
+        get_latest_root_version_in_state_merkle_db(&state_merkle_db).unwrap(),
+        Some(2)
+    );
+}
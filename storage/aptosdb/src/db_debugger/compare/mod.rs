@@ -0,0 +1,114 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{root_exists_at_version, DbDir},
+    schema::{
+        jellyfish_merkle_node::JellyfishMerkleNodeSchema, transaction_info::TransactionInfoSchema,
+    },
+};
+use anyhow::{ensure, Result};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_jellyfish_merkle::node_type::NodeKey;
+use aptos_types::transaction::Version;
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(about = "Compare the state root and the TransactionInfo of two dbs at a version.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    #[clap(long, parse(from_os_str))]
+    other_db_dir: PathBuf,
+
+    #[clap(long)]
+    version: Version,
+}
+
+struct Hashes {
+    root_hash: Option<HashValue>,
+    txn_info_hash: Option<HashValue>,
+}
+
+impl Hashes {
+    fn read(db_dir: &DbDir, version: Version) -> Result<Self> {
+        let state_merkle_db = db_dir.open_state_merkle_db()?;
+        let root_hash = state_merkle_db
+            .get::<JellyfishMerkleNodeSchema>(&NodeKey::new_empty_path(version))?
+            .map(|root| root.hash());
+        let ledger_db = db_dir.open_ledger_db()?;
+        let txn_info_hash = ledger_db
+            .get::<TransactionInfoSchema>(&version)?
+            .map(|txn_info| txn_info.hash());
+
+        Ok(Self {
+            root_hash,
+            txn_info_hash,
+        })
+    }
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        println!(
+            "* Comparing {:?} and {:?} at version {}.\n",
+            self.db_dir.as_ref(),
+            self.other_db_dir,
+            self.version
+        );
+
+        let other_db_dir = DbDir::from(self.other_db_dir);
+        let mut root_exists = false;
+        for db_dir in [&self.db_dir, &other_db_dir] {
+            root_exists |= root_exists_at_version(&db_dir.open_state_merkle_db()?, self.version)?;
+        }
+        ensure!(
+            root_exists,
+            "State root at version {} is missing in both dbs, pick a version with a state snapshot.",
+            self.version
+        );
+
+        let hashes = Hashes::read(&self.db_dir, self.version)?;
+        let other_hashes = Hashes::read(&other_db_dir, self.version)?;
+        ensure!(
+            hashes.txn_info_hash.is_some() || other_hashes.txn_info_hash.is_some(),
+            "TransactionInfo at version {} is missing in both dbs.",
+            self.version
+        );
+
+        let roots_match =
+            Self::print_comparison("State root", hashes.root_hash, other_hashes.root_hash);
+        let txn_infos_match = Self::print_comparison(
+            "TransactionInfo",
+            hashes.txn_info_hash,
+            other_hashes.txn_info_hash,
+        );
+        ensure!(
+            roots_match && txn_infos_match,
+            "The dbs differ at version {}.",
+            self.version
+        );
+        println!("Done.");
+
+        Ok(())
+    }
+
+    /// Prints whether the hashes match. Both being missing is checked beforehand.
+    fn print_comparison(
+        name: &str,
+        hash: Option<HashValue>,
+        other_hash: Option<HashValue>,
+    ) -> bool {
+        let matches = hash.is_some() && hash == other_hash;
+        println!(
+            "{:<16} {:?} vs {:?}: {}",
+            name,
+            hash,
+            other_hash,
+            if matches { "match" } else { "MISMATCH" },
+        );
+        matches
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::DbDir,
+    schema::{
+        epoch_by_version::EpochByVersionSchema, jellyfish_merkle_node::JellyfishMerkleNodeSchema,
+        state_value::StateValueSchema, transaction::TransactionSchema,
+        transaction_accumulator::TransactionAccumulatorSchema, write_set::WriteSetSchema,
+    },
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{
+    schema::{Schema, SeekKeyCodec},
+    ReadOptions, DB,
+};
+use aptos_types::{
+    proof::position::{num_frozen_nodes_in_accumulator, Position},
+    transaction::Version,
+};
+use clap::{ArgEnum, Parser};
+use std::fmt::Debug;
+
+#[derive(ArgEnum, Clone, Copy, Debug)]
+pub enum SchemaName {
+    Transaction,
+    WriteSet,
+    StateValue,
+    JmtNode,
+    Accumulator,
+    EpochByVersion,
+}
+
+#[derive(Parser)]
+#[clap(about = "Print the rows of a schema written in [start_version, end_version).")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    #[clap(arg_enum)]
+    schema: SchemaName,
+
+    start_version: Version,
+
+    end_version: Version,
+
+    /// Stop after printing this many rows.
+    #[clap(long)]
+    limit: Option<usize>,
+
+    /// Only print the number of rows in the range.
+    #[clap(long)]
+    count_only: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        ensure!(
+            self.start_version <= self.end_version,
+            "start_version {} is larger than end_version {}.",
+            self.start_version,
+            self.end_version,
+        );
+
+        match self.schema {
+            SchemaName::Transaction => {
+                self.dump_version_keyed::<TransactionSchema>(&self.db_dir.open_ledger_db()?)
+            },
+            SchemaName::WriteSet => {
+                self.dump_version_keyed::<WriteSetSchema>(&self.db_dir.open_ledger_db()?)
+            },
+            SchemaName::EpochByVersion => {
+                self.dump_version_keyed::<EpochByVersionSchema>(&self.db_dir.open_ledger_db()?)
+            },
+            SchemaName::StateValue => {
+                let db = self.db_dir.open_ledger_db()?;
+                let mut read_opts = ReadOptions::default();
+                // StateValueSchema is keyed by state key first, so scan all keys in total order.
+                read_opts.set_total_order_seek(true);
+                let mut iter = db.iter::<StateValueSchema>(read_opts)?;
+                iter.seek_to_first();
+                self.print_rows(iter.filter(|row| {
+                    row.as_ref().map_or(true, |((_, version), _)| {
+                        *version >= self.start_version && *version < self.end_version
+                    })
+                }))
+            },
+            SchemaName::JmtNode => {
+                let db = self.db_dir.open_state_merkle_db()?;
+                let mut iter = db.iter::<JellyfishMerkleNodeSchema>(ReadOptions::default())?;
+                iter.seek(&(self.start_version, 0))?;
+                self.print_rows(iter.take_while(|row| {
+                    row.as_ref()
+                        .map_or(true, |(node_key, _)| node_key.version() < self.end_version)
+                }))
+            },
+            SchemaName::Accumulator => {
+                let db = self.db_dir.open_ledger_db()?;
+                // Nodes frozen by appending the leaves in [start_version, end_version).
+                let start_index = num_frozen_nodes_in_accumulator(self.start_version);
+                let end_index = num_frozen_nodes_in_accumulator(self.end_version);
+                let mut iter = db.iter::<TransactionAccumulatorSchema>(ReadOptions::default())?;
+                iter.seek(&Position::from_postorder_index(start_index)?)?;
+                self.print_rows(iter.take_while(|row| {
+                    row.as_ref().map_or(true, |(position, _)| {
+                        position.to_postorder_index() < end_index
+                    })
+                }))
+            },
+        }
+    }
+
+    fn dump_version_keyed<S>(&self, db: &DB) -> Result<()>
+    where
+        S: Schema<Key = Version>,
+        Version: SeekKeyCodec<S>,
+    {
+        let mut iter = db.iter::<S>(ReadOptions::default())?;
+        iter.seek(&self.start_version)?;
+        self.print_rows(iter.take_while(|row| {
+            row.as_ref()
+                .map_or(true, |(version, _)| *version < self.end_version)
+        }))
+    }
+
+    fn print_rows<K: Debug, V: Debug>(
+        &self,
+        rows: impl Iterator<Item = Result<(K, V)>>,
+    ) -> Result<()> {
+        let mut num_rows = 0;
+        for row in rows {
+            let (key, value) = row?;
+            if !self.count_only {
+                if Some(num_rows) == self.limit {
+                    println!("Reached limit.");
+                    break;
+                }
+                println!("{:?}\n{:#?}\n", key, value);
+            }
+            num_rows += 1;
+        }
+        println!("{} rows.", num_rows);
+
+        Ok(())
+    }
+}
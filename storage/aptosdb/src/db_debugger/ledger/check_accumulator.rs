@@ -0,0 +1,125 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{get_current_version_in_ledger_db, get_ledger_pruner_progress, DbDir},
+    schema::transaction_accumulator::TransactionAccumulatorSchema,
+};
+use anyhow::{bail, format_err, Result};
+use aptos_schemadb::{ReadOptions, DB};
+use aptos_types::{
+    proof::position::{num_frozen_nodes_in_accumulator, Position},
+    transaction::Version,
+};
+use clap::Parser;
+use std::ops::{ControlFlow, Range};
+
+#[derive(Parser)]
+#[clap(about = "Check the number of accumulator nodes matches the number of transactions.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let db = self.db_dir.open_ledger_db()?;
+        let version = get_current_version_in_ledger_db(&db)?
+            .ok_or_else(|| format_err!("Ledger db is empty."))?;
+        println!("Current ledger db version: {}", version);
+
+        let expected_num_nodes = num_frozen_nodes_in_accumulator(version + 1);
+        println!("Expecting {} frozen nodes.", expected_num_nodes);
+        match find_accumulator_mismatch(&db, version + 1)? {
+            None => {
+                println!("Done.");
+                Ok(())
+            },
+            Some(mismatch) => bail!(
+                "Found mismatch: expecting {} nodes, got {}. First divergence at postorder index \
+                 {}, which is frozen at version {}.",
+                expected_num_nodes,
+                mismatch.num_nodes,
+                mismatch.first_divergence,
+                mismatch.diverged_at_version,
+            ),
+        }
+    }
+}
+
+pub struct AccumulatorMismatch {
+    /// One past the postorder index of the last node found.
+    pub num_nodes: u64,
+    /// Postorder index of the first node that is missing or unexpected.
+    pub first_divergence: u64,
+    /// The version at which the first diverging node is frozen.
+    pub diverged_at_version: Version,
+}
+
+/// Checks that the transaction accumulator has exactly the frozen nodes of an accumulator with
+/// `num_leaves` leaves, returning where it diverges if not. Nodes frozen before the ledger pruner
+/// progress may have been pruned, so they are not expected.
+pub fn find_accumulator_mismatch(
+    ledger_db: &DB,
+    num_leaves: u64,
+) -> Result<Option<AccumulatorMismatch>> {
+    let expected_num_nodes = num_frozen_nodes_in_accumulator(num_leaves);
+    let mut iter = ledger_db.iter::<TransactionAccumulatorSchema>(ReadOptions::default())?;
+    iter.seek_to_last();
+    let num_nodes = iter
+        .next()
+        .transpose()?
+        .map_or(0, |(position, _)| position.to_postorder_index() + 1);
+    if num_nodes == expected_num_nodes {
+        return Ok(None);
+    }
+
+    let pruner_progress = get_ledger_pruner_progress(ledger_db)?;
+    let start_index = num_frozen_nodes_in_accumulator(pruner_progress).min(expected_num_nodes);
+    // Without missing nodes, the first divergence is the first excess node.
+    let mut first_divergence = expected_num_nodes;
+    for_each_missing_accumulator_range(ledger_db, start_index..expected_num_nodes, |missing| {
+        first_divergence = missing.start;
+        Ok(ControlFlow::Break(()))
+    })?;
+
+    Ok(Some(AccumulatorMismatch {
+        num_nodes,
+        first_divergence,
+        diverged_at_version: get_version_frozen_at(first_divergence)?,
+    }))
+}
+
+/// Calls `on_missing` with each run of postorder indices in `range` that have no accumulator
+/// node, in order, until it breaks.
+pub fn for_each_missing_accumulator_range(
+    ledger_db: &DB,
+    range: Range<u64>,
+    mut on_missing: impl FnMut(Range<u64>) -> Result<ControlFlow<()>>,
+) -> Result<()> {
+    let mut next_index = range.start;
+    let mut iter = ledger_db.iter::<TransactionAccumulatorSchema>(ReadOptions::default())?;
+    iter.seek(&Position::from_postorder_index(range.start)?)?;
+    for item in iter {
+        let (position, _) = item?;
+        let index = position.to_postorder_index();
+        if index >= range.end {
+            break;
+        }
+        if index > next_index && on_missing(next_index..index)?.is_break() {
+            return Ok(());
+        }
+        next_index = index + 1;
+    }
+    if next_index < range.end {
+        on_missing(next_index..range.end)?;
+    }
+    Ok(())
+}
+
+/// Returns the version at which the accumulator node at `postorder_index` becomes frozen.
+pub fn get_version_frozen_at(postorder_index: u64) -> Result<Version> {
+    let position = Position::from_postorder_index(postorder_index)?;
+    // A node becomes frozen when the right most leaf under it is appended.
+    Ok(position.right_most_child().to_inorder_index() >> 1)
+}
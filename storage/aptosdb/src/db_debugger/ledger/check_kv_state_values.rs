@@ -0,0 +1,145 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{DbDir, PAGE_SIZE},
+    schema::{stale_state_value_index::StaleStateValueIndexSchema, state_value::StateValueSchema},
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{ReadOptions, DB};
+use aptos_types::{state_store::state_value::StaleStateValueIndex, transaction::Version};
+use clap::Parser;
+
+#[derive(Parser)]
+#[clap(
+    about = "Check that every state value referenced by the stale state value index lives in \
+    exactly one of the ledger db and the K/V db."
+)]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    start_version: Version,
+
+    end_version: Version,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        ensure!(
+            self.start_version <= self.end_version,
+            "start_version {} is larger than end_version {}.",
+            self.start_version,
+            self.end_version,
+        );
+
+        let ledger_db = self.db_dir.open_ledger_db()?;
+        let kv_db = self.db_dir.open_kv_db()?;
+        println!(
+            "* Checking state values that became stale in [{}, {}).\n",
+            self.start_version, self.end_version
+        );
+
+        let mut num_in_both = 0;
+        let mut num_in_neither = 0;
+        let num_checked = find_inconsistent_state_values(
+            &ledger_db,
+            Some(&kv_db),
+            self.start_version,
+            self.end_version,
+            |index, in_both| {
+                if num_in_both + num_in_neither < PAGE_SIZE {
+                    println!(
+                        "version: {:<20} stale since: {:<20} {} state key: {:?}",
+                        index.version,
+                        index.stale_since_version,
+                        if in_both {
+                            "in both dbs"
+                        } else {
+                            "in neither db"
+                        },
+                        index.state_key
+                    );
+                }
+                if in_both {
+                    num_in_both += 1;
+                } else {
+                    num_in_neither += 1;
+                }
+            },
+        )?;
+
+        println!(
+            "\nChecked {} state values: {} in both dbs, {} in neither db.",
+            num_checked, num_in_both, num_in_neither,
+        );
+        ensure!(
+            num_in_both + num_in_neither == 0,
+            "Found inconsistent state values."
+        );
+        println!("Done.");
+
+        Ok(())
+    }
+}
+
+/// Checks the state value of every stale state value index entry in `[start_version,
+/// end_version)` of either db lives in exactly one of the ledger db and the K/V db, calling
+/// `on_inconsistency` with each inconsistent entry and whether its value is in both dbs
+/// (otherwise it's in neither). Entries are checked as they are read, so memory use doesn't grow
+/// with the range. Returns the number of entries checked.
+pub fn find_inconsistent_state_values(
+    ledger_db: &DB,
+    kv_db: Option<&DB>,
+    start_version: Version,
+    end_version: Version,
+    mut on_inconsistency: impl FnMut(StaleStateValueIndex, bool),
+) -> Result<usize> {
+    let mut num_checked = 0;
+    let mut check = |index: StaleStateValueIndex| -> Result<()> {
+        let key = (index.state_key.clone(), index.version);
+        let in_ledger_db = ledger_db.get::<StateValueSchema>(&key)?.is_some();
+        let in_kv_db = match kv_db {
+            Some(kv_db) => kv_db.get::<StateValueSchema>(&key)?.is_some(),
+            None => false,
+        };
+        if in_ledger_db == in_kv_db {
+            on_inconsistency(index, in_ledger_db);
+        }
+        num_checked += 1;
+        Ok(())
+    };
+
+    for_each_stale_index(ledger_db, start_version, end_version, &mut check)?;
+    if let Some(kv_db) = kv_db {
+        for_each_stale_index(kv_db, start_version, end_version, |index| {
+            // Entries also indexed in the ledger db were checked in the first pass.
+            if ledger_db
+                .get::<StaleStateValueIndexSchema>(&index)?
+                .is_some()
+            {
+                return Ok(());
+            }
+            check(index)
+        })?;
+    }
+    Ok(num_checked)
+}
+
+fn for_each_stale_index(
+    db: &DB,
+    start_version: Version,
+    end_version: Version,
+    mut f: impl FnMut(StaleStateValueIndex) -> Result<()>,
+) -> Result<()> {
+    let mut iter = db.iter::<StaleStateValueIndexSchema>(ReadOptions::default())?;
+    iter.seek(&start_version)?;
+    for item in iter {
+        let (index, _) = item?;
+        if index.stale_since_version >= end_version {
+            break;
+        }
+        f(index)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{get_current_version_in_ledger_db, DbDir, PAGE_SIZE},
+    schema::version_data::VersionDataSchema,
+};
+use anyhow::{ensure, format_err, Result};
+use aptos_schemadb::{ReadOptions, DB};
+use aptos_types::transaction::Version;
+use clap::Parser;
+
+#[derive(Parser)]
+#[clap(about = "Check VersionDataSchema has an entry for every version up to the ledger version.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// Defaults to the oldest version in VersionDataSchema, since the pruner deletes the entries
+    /// of pruned versions.
+    #[clap(long)]
+    start_version: Option<Version>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let db = self.db_dir.open_ledger_db()?;
+        let ledger_db_version = get_current_version_in_ledger_db(&db)?
+            .ok_or_else(|| format_err!("Ledger db is empty."))?;
+        let start_version = match self.start_version {
+            Some(start_version) => start_version,
+            None => get_oldest_version_data_version(&db)?.unwrap_or(0),
+        };
+        println!(
+            "* Checking VersionDataSchema in [{}, {}].\n",
+            start_version, ledger_db_version
+        );
+
+        let gaps = find_version_data_gaps(&db, start_version, ledger_db_version)?;
+        for (start, end) in gaps.iter().take(PAGE_SIZE) {
+            println!("missing versions [{}, {})", start, end);
+        }
+        let num_missing: Version = gaps.iter().map(|(start, end)| end - start).sum();
+        println!(
+            "\nFound {} missing versions in {} gaps.",
+            num_missing,
+            gaps.len()
+        );
+        ensure!(
+            gaps.is_empty(),
+            "VersionDataSchema is missing versions, the first one is {}.",
+            gaps[0].0,
+        );
+        println!("Done.");
+
+        Ok(())
+    }
+}
+
+/// Returns the version of the oldest VersionDataSchema entry, or `None` if there is none.
+pub fn get_oldest_version_data_version(ledger_db: &DB) -> Result<Option<Version>> {
+    let mut iter = ledger_db.iter::<VersionDataSchema>(ReadOptions::default())?;
+    iter.seek_to_first();
+    Ok(iter.next().transpose()?.map(|(version, _)| version))
+}
+
+/// Returns the `[start, end)` ranges of versions in `[start_version, end_version]` that are
+/// missing from VersionDataSchema.
+pub fn find_version_data_gaps(
+    ledger_db: &DB,
+    start_version: Version,
+    end_version: Version,
+) -> Result<Vec<(Version, Version)>> {
+    let mut gaps = Vec::new();
+    let mut expected_version = start_version;
+    let mut iter = ledger_db.iter::<VersionDataSchema>(ReadOptions::default())?;
+    iter.seek(&start_version)?;
+    for item in iter {
+        let (version, _) = item?;
+        if version > end_version {
+            break;
+        }
+        if version > expected_version {
+            gaps.push((expected_version, version));
+        }
+        expected_version = version + 1;
+    }
+    if expected_version <= end_version {
+        gaps.push((expected_version, end_version + 1));
+    }
+    Ok(gaps)
+}
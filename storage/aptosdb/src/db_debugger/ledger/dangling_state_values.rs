@@ -0,0 +1,163 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{
+        get_current_version_in_ledger_db, DbDir, ErrorSkipper, DELETE_BATCH_SIZE, PAGE_SIZE,
+    },
+    schema::{stale_state_value_index::StaleStateValueIndexSchema, state_value::StateValueSchema},
+    KV_DB_NAME, LEDGER_DB_NAME,
+};
+use anyhow::{ensure, format_err, Result};
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
+use aptos_types::{state_store::state_key::StateKey, transaction::Version};
+use clap::Parser;
+use std::collections::HashSet;
+
+#[derive(Parser)]
+#[clap(
+    about = "Find StateValueSchema entries newer than the target version that are not referenced by \
+    any StaleStateValueIndexSchema entry, in the ledger db and the K/V db."
+)]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// Defaults to the current ledger db version. Can't be below it, since the live state values
+    /// of committed versions are not referenced by any stale index entry.
+    #[clap(long)]
+    target_version: Option<Version>,
+
+    /// Delete the entries found.
+    #[clap(long)]
+    fix: bool,
+
+    /// Log and skip rows that can't be read instead of aborting.
+    #[clap(long)]
+    continue_on_error: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let ledger_db = if self.fix {
+            self.db_dir.open_ledger_db_for_write()?
+        } else {
+            self.db_dir.open_ledger_db()?
+        };
+        let kv_db = match (self.db_dir.has_kv_db(), self.fix) {
+            (false, _) => None,
+            (true, false) => Some(self.db_dir.open_kv_db()?),
+            (true, true) => Some(self.db_dir.open_kv_db_for_write()?),
+        };
+        let ledger_db_version = get_current_version_in_ledger_db(&ledger_db)?
+            .ok_or_else(|| format_err!("Ledger db is empty."))?;
+        let target_version = self.target_version.unwrap_or(ledger_db_version);
+        ensure!(
+            target_version >= ledger_db_version,
+            "Target version ({}) must be >= current ledger db version ({}).",
+            target_version,
+            ledger_db_version,
+        );
+        println!(
+            "* Looking for dangling state values after version {}.\n",
+            target_version
+        );
+
+        let mut dbs = vec![(LEDGER_DB_NAME, &ledger_db)];
+        if let Some(kv_db) = &kv_db {
+            dbs.push((KV_DB_NAME, kv_db));
+        }
+        let mut skipper = ErrorSkipper::new(self.continue_on_error);
+        // Only entries of uncommitted versions are collected, so this stays small.
+        let mut referenced = HashSet::new();
+        for (_, db) in &dbs {
+            let mut iter = db.iter::<StaleStateValueIndexSchema>(ReadOptions::default())?;
+            iter.seek(&(target_version + 1))?;
+            for item in iter {
+                let (index, _) = match skipper.check(item)? {
+                    Some(row) => row,
+                    None => continue,
+                };
+                if index.version > target_version {
+                    referenced.insert((index.state_key, index.version));
+                }
+            }
+        }
+        println!(
+            "{} state values after version {} are referenced by the stale index.",
+            referenced.len(),
+            target_version
+        );
+
+        let mut num_dangling = 0;
+        for (name, db) in &dbs {
+            let num_found =
+                self.find_dangling(db, target_version, &referenced, num_dangling, &mut skipper)?;
+            println!("Found {} dangling state values in {}.", num_found, name);
+            num_dangling += num_found;
+        }
+
+        println!("\nFound {} dangling state values.", num_dangling);
+        if self.fix {
+            println!("Deleted {} dangling state values.", num_dangling);
+        }
+        if self.continue_on_error {
+            println!(
+                "Skipped {} rows that couldn't be read.",
+                skipper.num_skipped()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Finds the state values in `db` after `target_version` not in `referenced`, deleting them
+    /// if `--fix` is set. `num_printed` is the number of entries already printed for other dbs.
+    fn find_dangling(
+        &self,
+        db: &DB,
+        target_version: Version,
+        referenced: &HashSet<(StateKey, Version)>,
+        num_printed: usize,
+        skipper: &mut ErrorSkipper,
+    ) -> Result<usize> {
+        let mut read_opts = ReadOptions::default();
+        // StateValueSchema uses a prefix extractor, scan in total order to visit all keys.
+        read_opts.set_total_order_seek(true);
+        let mut iter = db.iter::<StateValueSchema>(read_opts)?;
+        iter.seek_to_first();
+
+        let mut num_dangling = 0;
+        let mut batch = SchemaBatch::new();
+        let mut batch_len = 0;
+        for item in iter {
+            let (key, _) = match skipper.check(item)? {
+                Some(row) => row,
+                None => continue,
+            };
+            if key.1 <= target_version || referenced.contains(&key) {
+                continue;
+            }
+
+            if num_printed + num_dangling < PAGE_SIZE {
+                println!("version: {:<20} state key: {:?}", key.1, key.0);
+            }
+            num_dangling += 1;
+
+            if self.fix {
+                batch.delete::<StateValueSchema>(&key)?;
+                batch_len += 1;
+                if batch_len == DELETE_BATCH_SIZE {
+                    db.write_schemas(batch)?;
+                    batch = SchemaBatch::new();
+                    batch_len = 0;
+                }
+            }
+        }
+        if batch_len > 0 {
+            db.write_schemas(batch)?;
+        }
+
+        Ok(num_dangling)
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{get_epoch_ending_at_or_before, get_epoch_endings_in_range, DbDir},
+    schema::ledger_info::LedgerInfoSchema,
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::DB;
+use aptos_types::transaction::Version;
+use clap::Parser;
+
+#[derive(Parser)]
+#[clap(about = "List epoch ending versions in [start_version, end_version).")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    start_version: Version,
+
+    end_version: Version,
+
+    /// Also print the closest epoch ending at or before this version.
+    #[clap(long)]
+    target_version: Option<Version>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        ensure!(
+            self.start_version <= self.end_version,
+            "start_version {} is larger than end_version {}.",
+            self.start_version,
+            self.end_version,
+        );
+
+        let db = self.db_dir.open_ledger_db()?;
+        println!(
+            "* Epoch endings in [{}, {}):\n",
+            self.start_version, self.end_version
+        );
+        for (version, epoch) in
+            get_epoch_endings_in_range(&db, self.start_version, self.end_version)?
+        {
+            Self::print_epoch_ending(&db, version, epoch)?;
+        }
+
+        if let Some(target_version) = self.target_version {
+            println!(
+                "\n* Closest epoch ending at or before version {}:\n",
+                target_version
+            );
+            match get_epoch_ending_at_or_before(&db, target_version)? {
+                Some((version, epoch)) => Self::print_epoch_ending(&db, version, epoch)?,
+                None => println!("None"),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn print_epoch_ending(db: &DB, version: Version, epoch: u64) -> Result<()> {
+        let ledger_info_exists = db.get::<LedgerInfoSchema>(&epoch)?.is_some();
+        println!(
+            "epoch {} ends at version {}, LedgerInfo {}",
+            epoch,
+            version,
+            if ledger_info_exists {
+                "present"
+            } else {
+                "missing"
+            },
+        );
+        Ok(())
+    }
+}
@@ -1,8 +1,17 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod check_accumulator;
+pub(crate) mod check_kv_state_values;
 mod check_range_proof;
 mod check_rxn_info_hashes;
+pub(crate) mod check_version_data;
+mod dangling_state_values;
+mod list_epoch_endings;
+pub(crate) mod orphaned_ledger_infos;
+pub(crate) mod rebuild_epoch_by_version;
+mod repair_accumulator;
+mod version_histogram;
 
 use anyhow::Result;
 
@@ -11,6 +20,15 @@ use anyhow::Result;
 pub enum Cmd {
     CheckTransactionInfoHashes(check_rxn_info_hashes::Cmd),
     CheckRangeProof(check_range_proof::Cmd),
+    CheckAccumulator(check_accumulator::Cmd),
+    ListEpochEndings(list_epoch_endings::Cmd),
+    DanglingStateValues(dangling_state_values::Cmd),
+    CheckKvStateValues(check_kv_state_values::Cmd),
+    OrphanedLedgerInfos(orphaned_ledger_infos::Cmd),
+    RebuildEpochByVersion(rebuild_epoch_by_version::Cmd),
+    CheckVersionData(check_version_data::Cmd),
+    VersionHistogram(version_histogram::Cmd),
+    RepairAccumulator(repair_accumulator::Cmd),
 }
 
 impl Cmd {
@@ -18,6 +36,18 @@ impl Cmd {
         match self {
             Self::CheckTransactionInfoHashes(cmd) => cmd.run(),
             Self::CheckRangeProof(cmd) => cmd.run(),
+            Self::CheckAccumulator(cmd) => cmd.run(),
+            Self::ListEpochEndings(cmd) => cmd.run(),
+            Self::DanglingStateValues(cmd) => cmd.run(),
+            Self::CheckKvStateValues(cmd) => cmd.run(),
+            Self::OrphanedLedgerInfos(cmd) => cmd.run(),
+            Self::RebuildEpochByVersion(cmd) => cmd.run(),
+            Self::CheckVersionData(cmd) => cmd.run(),
+            Self::VersionHistogram(cmd) => cmd.run(),
+            Self::RepairAccumulator(cmd) => cmd.run(),
         }
     }
 }
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,126 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::{
+        common::{get_epoch_endings_in_range, DbDir},
+        ledger::rebuild_epoch_by_version::repair_epoch_by_version,
+    },
+    schema::{epoch_by_version::EpochByVersionSchema, ledger_info::LedgerInfoSchema},
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{ReadOptions, DB};
+use aptos_types::transaction::Version;
+use clap::Parser;
+
+#[derive(Parser)]
+#[clap(
+    about = "Find epoch ending LedgerInfoSchema entries in [start_version, end_version) without a \
+    matching EpochByVersionSchema entry, and vice versa."
+)]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    start_version: Version,
+
+    end_version: Version,
+
+    /// Repair the EpochByVersionSchema entries of the orphaned LedgerInfos the way
+    /// rebuild-epoch-by-version does. The signed LedgerInfos are the source of truth and are never
+    /// deleted.
+    #[clap(long)]
+    fix: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        ensure!(
+            self.start_version <= self.end_version,
+            "start_version {} is larger than end_version {}.",
+            self.start_version,
+            self.end_version,
+        );
+
+        let db = if self.fix {
+            self.db_dir.open_ledger_db_for_write()?
+        } else {
+            self.db_dir.open_ledger_db()?
+        };
+        println!(
+            "* Checking epoch endings in [{}, {}).\n",
+            self.start_version, self.end_version
+        );
+
+        let orphaned_ledger_infos =
+            find_orphaned_ledger_infos(&db, self.start_version, self.end_version)?;
+        for (epoch, version) in &orphaned_ledger_infos {
+            println!(
+                "LedgerInfo of epoch {} ends at version {}, EpochByVersion missing",
+                epoch, version
+            );
+        }
+        let missing_ledger_infos =
+            find_epoch_endings_without_ledger_info(&db, self.start_version, self.end_version)?;
+        for (version, epoch) in &missing_ledger_infos {
+            println!(
+                "EpochByVersion says epoch {} ends at version {}, LedgerInfo missing",
+                epoch, version
+            );
+        }
+
+        println!(
+            "\nFound {} orphaned LedgerInfos, {} epoch endings without LedgerInfo.",
+            orphaned_ledger_infos.len(),
+            missing_ledger_infos.len(),
+        );
+
+        if self.fix && !orphaned_ledger_infos.is_empty() {
+            println!();
+            repair_epoch_by_version(&db, &orphaned_ledger_infos, true)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `(epoch, version)` of every epoch ending LedgerInfo in `[start_version, end_version)`
+/// without a matching EpochByVersionSchema entry.
+pub fn find_orphaned_ledger_infos(
+    ledger_db: &DB,
+    start_version: Version,
+    end_version: Version,
+) -> Result<Vec<(u64, Version)>> {
+    let mut ret = Vec::new();
+    let mut iter = ledger_db.iter::<LedgerInfoSchema>(ReadOptions::default())?;
+    iter.seek_to_first();
+    for item in iter {
+        let (epoch, ledger_info_with_sigs) = item?;
+        let ledger_info = ledger_info_with_sigs.ledger_info();
+        let version = ledger_info.version();
+        // The latest LedgerInfo is stored even if it doesn't end an epoch.
+        if !ledger_info.ends_epoch() || version < start_version || version >= end_version {
+            continue;
+        }
+        if ledger_db.get::<EpochByVersionSchema>(&version)? != Some(epoch) {
+            ret.push((epoch, version));
+        }
+    }
+    Ok(ret)
+}
+
+/// Returns `(version, epoch)` of every epoch ending in `[start_version, end_version)` without a
+/// LedgerInfo.
+pub fn find_epoch_endings_without_ledger_info(
+    ledger_db: &DB,
+    start_version: Version,
+    end_version: Version,
+) -> Result<Vec<(Version, u64)>> {
+    let mut ret = Vec::new();
+    for (version, epoch) in get_epoch_endings_in_range(ledger_db, start_version, end_version)? {
+        if ledger_db.get::<LedgerInfoSchema>(&epoch)?.is_none() {
+            ret.push((version, epoch));
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,131 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{DbDir, ErrorSkipper},
+    schema::{epoch_by_version::EpochByVersionSchema, ledger_info::LedgerInfoSchema},
+};
+use anyhow::Result;
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
+use aptos_types::transaction::Version;
+use clap::Parser;
+use std::collections::HashMap;
+
+#[derive(Parser)]
+#[clap(
+    about = "Rebuild EpochByVersionSchema entries from the epoch ending LedgerInfos in \
+    LedgerInfoSchema."
+)]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// Write the changes. Without it, they are only printed.
+    #[clap(long)]
+    fix: bool,
+
+    /// Log and skip rows that can't be read instead of aborting.
+    #[clap(long)]
+    continue_on_error: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let db = if self.fix {
+            self.db_dir.open_ledger_db_for_write()?
+        } else {
+            self.db_dir.open_ledger_db()?
+        };
+        println!("* Rebuilding EpochByVersionSchema from LedgerInfoSchema.\n");
+
+        let mut skipper = ErrorSkipper::new(self.continue_on_error);
+        let mut epoch_endings = Vec::new();
+        let mut iter = db.iter::<LedgerInfoSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (epoch, ledger_info_with_sigs) = match skipper.check(item)? {
+                Some(row) => row,
+                None => continue,
+            };
+            let ledger_info = ledger_info_with_sigs.ledger_info();
+            // The latest LedgerInfo is stored even if it doesn't end an epoch.
+            if ledger_info.ends_epoch() {
+                epoch_endings.push((epoch, ledger_info.version()));
+            }
+        }
+        repair_epoch_by_version(&db, &epoch_endings, self.fix)?;
+
+        if self.continue_on_error {
+            println!(
+                "Skipped {} rows that couldn't be read.",
+                skipper.num_skipped()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Makes EpochByVersionSchema agree with the epoch ending LedgerInfos, given as `(epoch,
+/// version)`: entries missing or wrong at those versions are written, and entries of those
+/// epochs at other versions are deleted. The changes are printed, and only written if `fix` is
+/// set.
+pub fn repair_epoch_by_version(
+    ledger_db: &DB,
+    epoch_endings: &[(u64, Version)],
+    fix: bool,
+) -> Result<()> {
+    let version_by_epoch: HashMap<_, _> = epoch_endings.iter().copied().collect();
+    let batch = SchemaBatch::new();
+
+    let mut num_deleted = 0;
+    let mut iter = ledger_db.iter::<EpochByVersionSchema>(ReadOptions::default())?;
+    iter.seek_to_first();
+    for item in iter {
+        let (version, epoch) = item?;
+        match version_by_epoch.get(&epoch) {
+            Some(expected_version) if *expected_version != version => {
+                println!(
+                    "version {}: deleting epoch {}, which ends at version {}",
+                    version, epoch, expected_version
+                );
+                batch.delete::<EpochByVersionSchema>(&version)?;
+                num_deleted += 1;
+            },
+            _ => (),
+        }
+    }
+
+    let mut num_added = 0;
+    let mut num_corrected = 0;
+    for (epoch, version) in epoch_endings {
+        match ledger_db.get::<EpochByVersionSchema>(version)? {
+            Some(existing) if existing == *epoch => continue,
+            Some(existing) => {
+                println!(
+                    "version {}: correcting epoch {} to {}",
+                    version, existing, epoch
+                );
+                num_corrected += 1;
+            },
+            None => {
+                println!("version {}: adding epoch {}", version, epoch);
+                num_added += 1;
+            },
+        }
+        batch.put::<EpochByVersionSchema>(version, epoch)?;
+    }
+
+    println!(
+        "\nAdded {} entries, corrected {} entries, deleted {} entries.",
+        num_added, num_corrected, num_deleted
+    );
+    if fix {
+        ledger_db.write_schemas(batch)?;
+        println!("Wrote the changes.");
+    } else {
+        println!("Rerun with --fix to write the changes.");
+    }
+
+    Ok(())
+}
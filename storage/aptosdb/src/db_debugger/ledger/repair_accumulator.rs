@@ -0,0 +1,116 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::{
+        common::{
+            get_current_version_in_ledger_db, get_ledger_pruner_progress, DbDir, DELETE_BATCH_SIZE,
+            PAGE_SIZE,
+        },
+        ledger::check_accumulator::{for_each_missing_accumulator_range, get_version_frozen_at},
+    },
+    schema::transaction_accumulator::TransactionAccumulatorSchema,
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{ReadOptions, SchemaBatch};
+use aptos_types::proof::position::{num_frozen_nodes_in_accumulator, Position};
+use clap::Parser;
+use std::ops::ControlFlow;
+
+#[derive(Parser)]
+#[clap(
+    about = "Report accumulator nodes missing from or beyond the frozen nodes of the given number \
+    of transactions, and delete the excess ones with --fix."
+)]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// Defaults to one past the current ledger db version.
+    #[clap(long)]
+    num_transactions: Option<u64>,
+
+    /// Delete the excess nodes. Without it, they are only counted.
+    #[clap(long)]
+    fix: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let db = if self.fix {
+            self.db_dir.open_ledger_db_for_write()?
+        } else {
+            self.db_dir.open_ledger_db()?
+        };
+        let ledger_db_version = get_current_version_in_ledger_db(&db)?;
+        let num_committed = ledger_db_version.map_or(0, |version| version + 1);
+        let num_transactions = self.num_transactions.unwrap_or(num_committed);
+        ensure!(num_transactions > 0, "Ledger db is empty.");
+        ensure!(
+            !self.fix || num_transactions >= num_committed,
+            "--fix requires num transactions ({}) >= current ledger db version + 1 ({}), \
+             otherwise nodes of committed transactions would be deleted.",
+            num_transactions,
+            num_committed,
+        );
+        let expected_num_nodes = num_frozen_nodes_in_accumulator(num_transactions);
+        // Nodes frozen before the ledger pruner progress may have been pruned.
+        let pruner_progress = get_ledger_pruner_progress(&db)?;
+        let start_index = num_frozen_nodes_in_accumulator(pruner_progress).min(expected_num_nodes);
+        println!(
+            "* Expecting frozen nodes [{}, {}) for {} transactions, pruned up to version {}.\n",
+            start_index, expected_num_nodes, num_transactions, pruner_progress
+        );
+
+        let mut num_missing = 0;
+        for_each_missing_accumulator_range(&db, start_index..expected_num_nodes, |missing| {
+            for postorder_index in missing.clone() {
+                if num_missing + postorder_index - missing.start >= PAGE_SIZE as u64 {
+                    break;
+                }
+                println!(
+                    "missing node at postorder index {}, frozen at version {}",
+                    postorder_index,
+                    get_version_frozen_at(postorder_index)?
+                );
+            }
+            num_missing += missing.end - missing.start;
+            Ok(ControlFlow::Continue(()))
+        })?;
+        ensure!(
+            num_missing == 0,
+            "The accumulator is missing {} nodes, which can't be repaired by deleting nodes. \
+             Nothing was deleted.",
+            num_missing
+        );
+
+        let mut num_excess = 0;
+        let mut batch = SchemaBatch::new();
+        let mut iter = db.iter::<TransactionAccumulatorSchema>(ReadOptions::default())?;
+        iter.seek(&Position::from_postorder_index(expected_num_nodes)?)?;
+        for item in iter {
+            let (position, _) = item?;
+            num_excess += 1;
+            if self.fix {
+                batch.delete::<TransactionAccumulatorSchema>(&position)?;
+                if num_excess % DELETE_BATCH_SIZE == 0 {
+                    db.write_schemas(batch)?;
+                    batch = SchemaBatch::new();
+                }
+            }
+        }
+
+        if self.fix {
+            db.write_schemas(batch)?;
+            println!("Deleted {} excess nodes.", num_excess);
+        } else {
+            println!(
+                "Found {} excess nodes, rerun with --fix to delete them.",
+                num_excess
+            );
+        }
+        println!("Done.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    check_accumulator::{find_accumulator_mismatch, AccumulatorMismatch},
+    check_version_data::find_version_data_gaps,
+    rebuild_epoch_by_version::repair_epoch_by_version,
+    repair_accumulator,
+};
+use crate::{
+    db_options::gen_ledger_cfds,
+    schema::{
+        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+        epoch_by_version::EpochByVersionSchema,
+        transaction_accumulator::TransactionAccumulatorSchema,
+        version_data::{VersionData, VersionDataSchema},
+    },
+    LedgerStore, TransactionStore, LEDGER_DB_NAME,
+};
+use anyhow::Result;
+use aptos_config::config::RocksdbConfigs;
+use aptos_crypto::HashValue;
+use aptos_rocksdb_options::gen_rocksdb_options;
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
+use aptos_temppath::TempPath;
+use aptos_types::{
+    proof::position::Position,
+    transaction::{ExecutionStatus, TransactionInfo, Version},
+};
+use clap::Parser;
+use std::sync::Arc;
+
+fn open_ledger_db(tmp_dir: &TempPath) -> Arc<DB> {
+    let rocksdb_config = RocksdbConfigs::default().ledger_db_config;
+    Arc::new(
+        DB::open_cf(
+            &gen_rocksdb_options(&rocksdb_config, false),
+            tmp_dir.path().join(LEDGER_DB_NAME),
+            LEDGER_DB_NAME,
+            gen_ledger_cfds(&rocksdb_config),
+        )
+        .unwrap(),
+    )
+}
+
+/// Commits the TransactionInfos and the accumulator of `num_txns` transactions, and prunes them
+/// before `pruner_progress` the way the ledger pruner does.
+fn init_ledger_db(tmp_dir: &TempPath, num_txns: u64, pruner_progress: Version) -> Arc<DB> {
+    let ledger_db = open_ledger_db(tmp_dir);
+    let txn_infos: Vec<_> = (0..num_txns)
+        .map(|_| {
+            TransactionInfo::new(
+                HashValue::random(),
+                HashValue::random(),
+                HashValue::random(),
+                None,
+                0,
+                ExecutionStatus::Success,
+            )
+        })
+        .collect();
+    let batch = SchemaBatch::new();
+    LedgerStore::new(Arc::clone(&ledger_db))
+        .put_transaction_infos(0, &txn_infos, &batch)
+        .unwrap();
+    ledger_db.write_schemas(batch).unwrap();
+
+    let batch = SchemaBatch::new();
+    TransactionStore::new(Arc::clone(&ledger_db))
+        .prune_transaction_accumulator(0, pruner_progress, &batch)
+        .unwrap();
+    batch
+        .put::<DbMetadataSchema>(
+            &DbMetadataKey::LedgerPrunerProgress,
+            &DbMetadataValue::Version(pruner_progress),
+        )
+        .unwrap();
+    ledger_db.write_schemas(batch).unwrap();
+    ledger_db
+}
+
+fn put_accumulator_nodes(ledger_db: &DB, postorder_indices: impl IntoIterator<Item = u64>) {
+    let batch = SchemaBatch::new();
+    for postorder_index in postorder_indices {
+        batch
+            .put::<TransactionAccumulatorSchema>(
+                &Position::from_postorder_index(postorder_index).unwrap(),
+                &HashValue::random(),
+            )
+            .unwrap();
+    }
+    ledger_db.write_schemas(batch).unwrap();
+}
+
+fn delete_accumulator_node(ledger_db: &DB, postorder_index: u64) {
+    let batch = SchemaBatch::new();
+    batch
+        .delete::<TransactionAccumulatorSchema>(
+            &Position::from_postorder_index(postorder_index).unwrap(),
+        )
+        .unwrap();
+    ledger_db.write_schemas(batch).unwrap();
+}
+
+fn get_accumulator_indices(ledger_db: &DB) -> Vec<u64> {
+    let mut iter = ledger_db
+        .iter::<TransactionAccumulatorSchema>(ReadOptions::default())
+        .unwrap();
+    iter.seek_to_first();
+    iter.map(|item| item.unwrap().0.to_postorder_index())
+        .collect()
+}
+
+fn run_repair_accumulator(tmp_dir: &TempPath, args: &[&str]) -> Result<()> {
+    let db_dir = tmp_dir.path().to_str().unwrap();
+    repair_accumulator::Cmd::try_parse_from(
+        ["repair-accumulator", "--db-dir", db_dir]
+            .iter()
+            .chain(args),
+    )?
+    .run()
+}
+
+#[test]
+fn test_repair_accumulator_deletes_excess_nodes_with_fix() {
+    let tmp_dir = TempPath::new();
+    // 10 transactions have 18 frozen nodes.
+    let ledger_db = init_ledger_db(&tmp_dir, 10, 6);
+    let frozen_indices = get_accumulator_indices(&ledger_db);
+    put_accumulator_nodes(&ledger_db, 18..21);
+    drop(ledger_db);
+
+    run_repair_accumulator(&tmp_dir, &[]).unwrap();
+    let ledger_db = open_ledger_db(&tmp_dir);
+    assert_eq!(
+        get_accumulator_indices(&ledger_db).len(),
+        frozen_indices.len() + 3
+    );
+    drop(ledger_db);
+
+    run_repair_accumulator(&tmp_dir, &["--fix"]).unwrap();
+    let ledger_db = open_ledger_db(&tmp_dir);
+    assert_eq!(get_accumulator_indices(&ledger_db), frozen_indices);
+}
+
+#[test]
+fn test_repair_accumulator_rejects_deleting_committed_nodes() {
+    let tmp_dir = TempPath::new();
+    let ledger_db = init_ledger_db(&tmp_dir, 10, 0);
+    let frozen_indices = get_accumulator_indices(&ledger_db);
+    drop(ledger_db);
+
+    assert!(run_repair_accumulator(&tmp_dir, &["--num-transactions", "5", "--fix"]).is_err());
+    let ledger_db = open_ledger_db(&tmp_dir);
+    assert_eq!(get_accumulator_indices(&ledger_db), frozen_indices);
+}
+
+#[test]
+fn test_repair_accumulator_missing_node_deletes_nothing() {
+    let tmp_dir = TempPath::new();
+    let ledger_db = init_ledger_db(&tmp_dir, 10, 6);
+    put_accumulator_nodes(&ledger_db, 18..19);
+    // Leaf 8, frozen after the pruner progress.
+    delete_accumulator_node(&ledger_db, 15);
+    let indices = get_accumulator_indices(&ledger_db);
+    drop(ledger_db);
+
+    assert!(run_repair_accumulator(&tmp_dir, &["--fix"]).is_err());
+    let ledger_db = open_ledger_db(&tmp_dir);
+    assert_eq!(get_accumulator_indices(&ledger_db), indices);
+}
+
+fn assert_mismatch(
+    mismatch: Option<AccumulatorMismatch>,
+    num_nodes: u64,
+    first_divergence: u64,
+    diverged_at_version: Version,
+) {
+    let mismatch = mismatch.unwrap();
+    assert_eq!(mismatch.num_nodes, num_nodes);
+    assert_eq!(mismatch.first_divergence, first_divergence);
+    assert_eq!(mismatch.diverged_at_version, diverged_at_version);
+}
+
+#[test]
+fn test_find_accumulator_mismatch_pruned() {
+    let tmp_dir = TempPath::new();
+    let ledger_db = init_ledger_db(&tmp_dir, 10, 6);
+    assert!(find_accumulator_mismatch(&ledger_db, 10).unwrap().is_none());
+}
+
+#[test]
+fn test_find_accumulator_mismatch_missing_tail_node() {
+    let tmp_dir = TempPath::new();
+    let ledger_db = init_ledger_db(&tmp_dir, 10, 6);
+    // The parent of leaves 8 and 9.
+    delete_accumulator_node(&ledger_db, 17);
+    assert_mismatch(
+        find_accumulator_mismatch(&ledger_db, 10).unwrap(),
+        17,
+        17,
+        9,
+    );
+}
+
+#[test]
+fn test_find_accumulator_mismatch_excess_nodes() {
+    let tmp_dir = TempPath::new();
+    let ledger_db = init_ledger_db(&tmp_dir, 10, 6);
+    // Leaves 10 and 11.
+    put_accumulator_nodes(&ledger_db, [18, 19]);
+    assert_mismatch(
+        find_accumulator_mismatch(&ledger_db, 10).unwrap(),
+        20,
+        18,
+        10,
+    );
+}
+
+#[test]
+fn test_find_accumulator_mismatch_unpruned() {
+    let tmp_dir = TempPath::new();
+    let ledger_db = init_ledger_db(&tmp_dir, 10, 0);
+    assert!(find_accumulator_mismatch(&ledger_db, 10).unwrap().is_none());
+    // The leaf at version 0.
+    delete_accumulator_node(&ledger_db, 0);
+    put_accumulator_nodes(&ledger_db, [18]);
+    assert_mismatch(find_accumulator_mismatch(&ledger_db, 10).unwrap(), 19, 0, 0);
+}
+
+fn put_version_data(ledger_db: &DB, versions: impl IntoIterator<Item = Version>) {
+    let batch = SchemaBatch::new();
+    for version in versions {
+        batch
+            .put::<VersionDataSchema>(&version, &VersionData {
+                state_items: 1,
+                total_state_bytes: 1,
+            })
+            .unwrap();
+    }
+    ledger_db.write_schemas(batch).unwrap();
+}
+
+#[test]
+fn test_find_version_data_gaps() {
+    let tmp_dir = TempPath::new();
+    let ledger_db = open_ledger_db(&tmp_dir);
+    assert_eq!(find_version_data_gaps(&ledger_db, 0, 9).unwrap(), vec![(
+        0, 10
+    )]);
+
+    put_version_data(&ledger_db, [2, 3, 5, 6, 7]);
+    assert_eq!(find_version_data_gaps(&ledger_db, 0, 9).unwrap(), vec![
+        (0, 2),
+        (4, 5),
+        (8, 10)
+    ]);
+    assert_eq!(find_version_data_gaps(&ledger_db, 2, 3).unwrap(), vec![]);
+    assert_eq!(find_version_data_gaps(&ledger_db, 5, 7).unwrap(), vec![]);
+    assert_eq!(find_version_data_gaps(&ledger_db, 3, 5).unwrap(), vec![(
+        4, 5
+    )]);
+}
+
+fn get_epoch_by_version(ledger_db: &DB) -> Vec<(Version, u64)> {
+    let mut iter = ledger_db
+        .iter::<EpochByVersionSchema>(ReadOptions::default())
+        .unwrap();
+    iter.seek_to_first();
+    iter.map(|item| item.unwrap()).collect()
+}
+
+#[test]
+fn test_repair_epoch_by_version() {
+    let tmp_dir = TempPath::new();
+    let ledger_db = open_ledger_db(&tmp_dir);
+    let batch = SchemaBatch::new();
+    // Epoch 1 at the wrong version, the wrong epoch at version 12, and epoch 3 missing.
+    for (version, epoch) in [(5, 0), (9, 1), (12, 7)] {
+        batch.put::<EpochByVersionSchema>(&version, &epoch).unwrap();
+    }
+    ledger_db.write_schemas(batch).unwrap();
+    let epoch_endings = [(0, 5), (1, 10), (2, 12), (3, 20)];
+
+    repair_epoch_by_version(&ledger_db, &epoch_endings, false).unwrap();
+    assert_eq!(get_epoch_by_version(&ledger_db), vec![
+        (5, 0),
+        (9, 1),
+        (12, 7)
+    ]);
+
+    repair_epoch_by_version(&ledger_db, &epoch_endings, true).unwrap();
+    assert_eq!(get_epoch_by_version(&ledger_db), vec![
+        (5, 0),
+        (10, 1),
+        (12, 2),
+        (20, 3)
+    ]);
+}
@@ -0,0 +1,123 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{DbDir, OutputFormat},
+    schema::{event::EventSchema, state_value::StateValueSchema, transaction::TransactionSchema},
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{schema::Schema, ReadOptions, DB};
+use aptos_types::transaction::Version;
+use clap::Parser;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Parser)]
+#[clap(
+    about = "Count transactions, state values and events in the ledger db per range of versions."
+)]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    #[clap(long, default_value = "1000000")]
+    bucket_size: Version,
+
+    #[clap(long, arg_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+#[derive(Default, Serialize)]
+struct Bucket {
+    start_version: Version,
+    end_version: Version,
+    num_transactions: usize,
+    num_state_values: usize,
+    num_events: usize,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        ensure!(self.bucket_size > 0, "bucket_size must be positive.");
+
+        let ledger_db = self.db_dir.open_ledger_db()?;
+        let mut buckets = BTreeMap::new();
+        self.count::<TransactionSchema>(
+            &ledger_db,
+            ReadOptions::default(),
+            |version| *version,
+            &mut buckets,
+            |bucket| &mut bucket.num_transactions,
+        )?;
+        self.count::<EventSchema>(
+            &ledger_db,
+            ReadOptions::default(),
+            |(version, _)| *version,
+            &mut buckets,
+            |bucket| &mut bucket.num_events,
+        )?;
+
+        let mut state_value_dbs = vec![ledger_db];
+        if self.db_dir.has_kv_db() {
+            state_value_dbs.push(self.db_dir.open_kv_db()?);
+        }
+        for db in &state_value_dbs {
+            let mut read_opts = ReadOptions::default();
+            // StateValueSchema uses a prefix extractor, scan in total order to visit all keys.
+            read_opts.set_total_order_seek(true);
+            self.count::<StateValueSchema>(
+                db,
+                read_opts,
+                |(_, version)| *version,
+                &mut buckets,
+                |bucket| &mut bucket.num_state_values,
+            )?;
+        }
+
+        let buckets: Vec<_> = buckets.into_values().collect();
+        match self.format {
+            OutputFormat::Text => {
+                println!(
+                    "{:<20} {:<20} {:>15} {:>15} {:>15}",
+                    "start_version", "end_version", "transactions", "state_values", "events"
+                );
+                for bucket in &buckets {
+                    println!(
+                        "{:<20} {:<20} {:>15} {:>15} {:>15}",
+                        bucket.start_version,
+                        bucket.end_version,
+                        bucket.num_transactions,
+                        bucket.num_state_values,
+                        bucket.num_events
+                    );
+                }
+            },
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&buckets)?),
+        }
+
+        Ok(())
+    }
+
+    fn count<S: Schema>(
+        &self,
+        db: &DB,
+        read_opts: ReadOptions,
+        version_of: impl Fn(&S::Key) -> Version,
+        buckets: &mut BTreeMap<Version, Bucket>,
+        counter: impl Fn(&mut Bucket) -> &mut usize,
+    ) -> Result<()> {
+        let mut iter = db.iter::<S>(read_opts)?;
+        iter.seek_to_first();
+        for item in iter {
+            let (key, _) = item?;
+            let start_version = version_of(&key) / self.bucket_size * self.bucket_size;
+            let bucket = buckets.entry(start_version).or_insert_with(|| Bucket {
+                start_version,
+                end_version: start_version.saturating_add(self.bucket_size),
+                ..Default::default()
+            });
+            *counter(bucket) += 1;
+        }
+        Ok(())
+    }
+}
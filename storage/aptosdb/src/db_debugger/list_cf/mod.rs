@@ -0,0 +1,57 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::DbDir,
+    db_options::{
+        kv_db_column_families, ledger_db_column_families, state_merkle_db_column_families,
+    },
+    KV_DB_NAME, LEDGER_DB_NAME, STATE_MERKLE_DB_NAME,
+};
+use anyhow::Result;
+use aptos_schemadb::{Options, DB};
+use clap::Parser;
+
+#[derive(Parser)]
+#[clap(about = "List the column families present in each db and whether the debugger knows them.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        self.db_dir.validate()?;
+        for (name, known_cfs) in [
+            (LEDGER_DB_NAME, ledger_db_column_families()),
+            (STATE_MERKLE_DB_NAME, state_merkle_db_column_families()),
+            (KV_DB_NAME, kv_db_column_families()),
+        ] {
+            let path = self.db_dir.as_ref().join(name);
+            println!("* {}:", name);
+            if !self.db_dir.has_db(name) {
+                println!("not found at {:?}\n", path);
+                continue;
+            }
+
+            let cfs = DB::list_cf(&Options::default(), &path)?;
+            for cf in &cfs {
+                let status = if known_cfs.iter().any(|known_cf| known_cf == cf) {
+                    "known"
+                } else {
+                    "UNKNOWN, won't be checked or repaired"
+                };
+                println!("{:<40} {}", cf, status);
+            }
+            for known_cf in known_cfs
+                .iter()
+                .filter(|known_cf| !cfs.iter().any(|cf| cf == *known_cf))
+            {
+                println!("{:<40} missing", known_cf);
+            }
+            println!();
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod check;
 mod checkpoint;
 mod common;
+mod compare;
+mod dump;
 mod ledger;
+mod list_cf;
 mod state_tree;
+mod status;
 
 use anyhow::Result;
 use clap::Parser;
@@ -18,6 +23,16 @@ pub enum Cmd {
 
     #[clap(subcommand)]
     Ledger(ledger::Cmd),
+
+    Status(status::Cmd),
+
+    Dump(dump::Cmd),
+
+    Compare(compare::Cmd),
+
+    Check(check::Cmd),
+
+    ListCf(list_cf::Cmd),
 }
 
 impl Cmd {
@@ -26,6 +41,11 @@ impl Cmd {
             Cmd::StateTree(cmd) => cmd.run(),
             Cmd::Checkpoint(cmd) => cmd.run(),
             Cmd::Ledger(cmd) => cmd.run(),
+            Cmd::Status(cmd) => cmd.run(),
+            Cmd::Dump(cmd) => cmd.run(),
+            Cmd::Compare(cmd) => cmd.run(),
+            Cmd::Check(cmd) => cmd.run(),
+            Cmd::ListCf(cmd) => cmd.run(),
         }
     }
 }
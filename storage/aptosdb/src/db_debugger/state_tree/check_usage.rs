@@ -0,0 +1,67 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{get_latest_root_version_in_state_merkle_db, DbDir},
+    schema::version_data::VersionDataSchema,
+    state_merkle_db::StateMerkleDb,
+};
+use anyhow::{ensure, format_err, Result};
+use aptos_schemadb::DB;
+use aptos_types::transaction::Version;
+use clap::Parser;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[clap(
+    about = "Check the state item count in VersionDataSchema matches the number of leaves in the \
+    state tree."
+)]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// Defaults to the version of the latest state root.
+    #[clap(long)]
+    version: Option<Version>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let ledger_db = self.db_dir.open_ledger_db()?;
+        let state_merkle_db = StateMerkleDb::new(Arc::new(self.db_dir.open_state_merkle_db()?), 0);
+        let version = match self.version {
+            Some(version) => version,
+            None => get_latest_root_version_in_state_merkle_db(&state_merkle_db)?
+                .ok_or_else(|| format_err!("No state root found."))?,
+        };
+        println!("* Checking state usage at version {}.\n", version);
+
+        let (state_items, leaf_count) =
+            get_state_items_and_leaf_count(&ledger_db, &state_merkle_db, version)?;
+        println!("VersionData state items: {:?}", state_items);
+        println!("State tree leaves: {}", leaf_count);
+        ensure!(
+            state_items == Some(leaf_count),
+            "State usage doesn't match the state tree at version {}.",
+            version
+        );
+        println!("Done.");
+
+        Ok(())
+    }
+}
+
+/// Returns the state item count recorded in VersionDataSchema at `version`, or `None` if there is
+/// no entry, along with the number of leaves in the state tree at `version`.
+pub fn get_state_items_and_leaf_count(
+    ledger_db: &DB,
+    state_merkle_db: &StateMerkleDb,
+    version: Version,
+) -> Result<(Option<usize>, usize)> {
+    let state_items = ledger_db
+        .get::<VersionDataSchema>(&version)?
+        .map(|data| data.state_items);
+    let leaf_count = state_merkle_db.get_leaf_count(version)?;
+    Ok((state_items, leaf_count))
+}
@@ -0,0 +1,130 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{
+        get_latest_root_version_in_state_merkle_db, DbDir, ErrorSkipper, DELETE_BATCH_SIZE,
+    },
+    schema::{
+        stale_node_index::StaleNodeIndexSchema,
+        stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
+    },
+};
+use anyhow::{ensure, Result};
+use aptos_jellyfish_merkle::StaleNodeIndex;
+use aptos_schemadb::{
+    schema::{Schema, SeekKeyCodec},
+    ReadOptions, SchemaBatch, DB,
+};
+use aptos_types::transaction::Version;
+use clap::Parser;
+
+#[derive(Parser)]
+#[clap(about = "Delete stale node index entries that became stale at or after a version.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// Must be above the latest state root, whose stale nodes still need to be pruned.
+    #[clap(long)]
+    start_version: Version,
+
+    /// Delete the entries found. Without it, they are only counted.
+    #[clap(long)]
+    fix: bool,
+
+    /// Log and skip rows that can't be read instead of aborting.
+    #[clap(long)]
+    continue_on_error: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let db = if self.fix {
+            self.db_dir.open_state_merkle_db_for_write()?
+        } else {
+            self.db_dir.open_state_merkle_db()?
+        };
+        if let Some(root_version) = get_latest_root_version_in_state_merkle_db(&db)? {
+            ensure!(
+                !self.fix || self.start_version > root_version,
+                "--fix requires start version ({}) > latest state root version ({}), otherwise \
+                 the pruner would never delete the nodes the entries point to.",
+                self.start_version,
+                root_version,
+            );
+        }
+        println!(
+            "* Looking for stale node index entries with stale_since_version >= {}. \n",
+            self.start_version
+        );
+
+        let mut skipper = ErrorSkipper::new(self.continue_on_error);
+        let num_found = delete_stale_node_index_at_or_after::<StaleNodeIndexSchema>(
+            &db,
+            self.start_version,
+            self.fix,
+            &mut skipper,
+        )?;
+        println!("Found {} StaleNodeIndexSchema entries.", num_found);
+        let num_found = delete_stale_node_index_at_or_after::<StaleNodeIndexCrossEpochSchema>(
+            &db,
+            self.start_version,
+            self.fix,
+            &mut skipper,
+        )?;
+        println!(
+            "Found {} StaleNodeIndexCrossEpochSchema entries.",
+            num_found
+        );
+        if self.fix {
+            println!("Deleted the entries found.");
+        }
+        if self.continue_on_error {
+            println!(
+                "Skipped {} rows that couldn't be read.",
+                skipper.num_skipped()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the entries of a stale node index schema whose `stale_since_version` is at or after
+/// `start_version`, deleting them if `fix` is set. Returns the number of entries found.
+fn delete_stale_node_index_at_or_after<S>(
+    db: &DB,
+    start_version: Version,
+    fix: bool,
+    skipper: &mut ErrorSkipper,
+) -> Result<usize>
+where
+    S: Schema<Key = StaleNodeIndex>,
+    Version: SeekKeyCodec<S>,
+{
+    let mut iter = db.iter::<S>(ReadOptions::default())?;
+    iter.seek(&start_version)?;
+
+    let mut num_found = 0;
+    let mut batch = SchemaBatch::new();
+    for item in iter {
+        let (index, _) = match skipper.check(item)? {
+            Some(row) => row,
+            None => continue,
+        };
+        num_found += 1;
+        if fix {
+            batch.delete::<S>(&index)?;
+            if num_found % DELETE_BATCH_SIZE == 0 {
+                db.write_schemas(batch)?;
+                batch = SchemaBatch::new();
+            }
+        }
+    }
+    if fix {
+        db.write_schemas(batch)?;
+    }
+
+    Ok(num_found)
+}
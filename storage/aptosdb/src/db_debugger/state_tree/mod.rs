@@ -1,8 +1,11 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod check_usage;
+mod delete_stale_node_index;
 mod get_path;
 mod get_snapshots;
+mod orphaned_nodes;
 
 use anyhow::Result;
 
@@ -11,6 +14,9 @@ use anyhow::Result;
 pub enum Cmd {
     GetSnapshots(get_snapshots::Cmd),
     GetPath(get_path::Cmd),
+    DeleteStaleNodeIndex(delete_stale_node_index::Cmd),
+    OrphanedNodes(orphaned_nodes::Cmd),
+    CheckUsage(check_usage::Cmd),
 }
 
 impl Cmd {
@@ -18,6 +24,12 @@ impl Cmd {
         match self {
             Self::GetSnapshots(cmd) => cmd.run(),
             Self::GetPath(cmd) => cmd.run(),
+            Self::DeleteStaleNodeIndex(cmd) => cmd.run(),
+            Self::OrphanedNodes(cmd) => cmd.run(),
+            Self::CheckUsage(cmd) => cmd.run(),
         }
     }
 }
+
+#[cfg(test)]
+mod test;
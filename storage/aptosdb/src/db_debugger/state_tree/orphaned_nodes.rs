@@ -0,0 +1,82 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{
+        get_latest_root_version_in_state_merkle_db, DbDir, DELETE_BATCH_SIZE, PAGE_SIZE,
+    },
+    schema::jellyfish_merkle_node::JellyfishMerkleNodeSchema,
+};
+use anyhow::{format_err, Result};
+use aptos_jellyfish_merkle::node_type::NodeKey;
+use aptos_schemadb::{ReadOptions, SchemaBatch};
+use clap::Parser;
+use std::collections::BTreeMap;
+
+#[derive(Parser)]
+#[clap(about = "Find JellyfishMerkleNodeSchema entries newer than the latest state root.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// Delete the nodes found.
+    #[clap(long)]
+    fix: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let db = if self.fix {
+            self.db_dir.open_state_merkle_db_for_write()?
+        } else {
+            self.db_dir.open_state_merkle_db()?
+        };
+        let root_version = get_latest_root_version_in_state_merkle_db(&db)?
+            .ok_or_else(|| format_err!("No state root found."))?;
+        println!(
+            "* Looking for nodes newer than the latest root at version {}.\n",
+            root_version
+        );
+
+        let mut num_nodes_by_version = BTreeMap::new();
+        let mut batch = SchemaBatch::new();
+        let mut batch_len = 0;
+        let mut iter = db.iter::<JellyfishMerkleNodeSchema>(ReadOptions::default())?;
+        iter.seek(&NodeKey::new_empty_path(root_version + 1))?;
+        for item in iter {
+            let (node_key, _) = item?;
+            *num_nodes_by_version.entry(node_key.version()).or_insert(0) += 1;
+
+            if self.fix {
+                batch.delete::<JellyfishMerkleNodeSchema>(&node_key)?;
+                batch_len += 1;
+                if batch_len == DELETE_BATCH_SIZE {
+                    db.write_schemas(batch)?;
+                    batch = SchemaBatch::new();
+                    batch_len = 0;
+                }
+            }
+        }
+        if batch_len > 0 {
+            db.write_schemas(batch)?;
+        }
+
+        for (version, num_nodes) in num_nodes_by_version.iter().take(PAGE_SIZE) {
+            println!("version: {:<20} nodes: {}", version, num_nodes);
+        }
+        if num_nodes_by_version.len() > PAGE_SIZE {
+            println!("...");
+        }
+        let num_orphaned: usize = num_nodes_by_version.values().sum();
+        println!(
+            "\nFound {} orphaned nodes across {} versions.",
+            num_orphaned,
+            num_nodes_by_version.len()
+        );
+        if self.fix {
+            println!("Deleted {} orphaned nodes.", num_orphaned);
+        }
+
+        Ok(())
+    }
+}
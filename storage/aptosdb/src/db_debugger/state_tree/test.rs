@@ -0,0 +1,101 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{delete_stale_node_index, orphaned_nodes};
+use crate::{
+    db_debugger::common::test::{open_state_merkle_db, put_nodes},
+    schema::{
+        jellyfish_merkle_node::JellyfishMerkleNodeSchema, stale_node_index::StaleNodeIndexSchema,
+    },
+};
+use anyhow::Result;
+use aptos_jellyfish_merkle::{node_type::NodeKey, StaleNodeIndex};
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
+use aptos_temppath::TempPath;
+use aptos_types::transaction::Version;
+use clap::Parser;
+
+fn get_node_versions(state_merkle_db: &DB) -> Vec<Version> {
+    let mut iter = state_merkle_db
+        .iter::<JellyfishMerkleNodeSchema>(ReadOptions::default())
+        .unwrap();
+    iter.seek_to_first();
+    iter.map(|item| item.unwrap().0.version()).collect()
+}
+
+fn run_orphaned_nodes(tmp_dir: &TempPath, args: &[&str]) -> Result<()> {
+    let db_dir = tmp_dir.path().to_str().unwrap();
+    orphaned_nodes::Cmd::try_parse_from(["orphaned-nodes", "--db-dir", db_dir].iter().chain(args))?
+        .run()
+}
+
+#[test]
+fn test_orphaned_nodes_deletes_nodes_above_latest_root_with_fix() {
+    let tmp_dir = TempPath::new();
+    let state_merkle_db = open_state_merkle_db(&tmp_dir);
+    put_nodes(&state_merkle_db, 0, true);
+    put_nodes(&state_merkle_db, 2, true);
+    put_nodes(&state_merkle_db, 3, false);
+    put_nodes(&state_merkle_db, 4, false);
+    drop(state_merkle_db);
+
+    run_orphaned_nodes(&tmp_dir, &[]).unwrap();
+    let state_merkle_db = open_state_merkle_db(&tmp_dir);
+    assert_eq!(get_node_versions(&state_merkle_db), vec![0, 0, 2, 2, 3, 4]);
+    drop(state_merkle_db);
+
+    run_orphaned_nodes(&tmp_dir, &["--fix"]).unwrap();
+    let state_merkle_db = open_state_merkle_db(&tmp_dir);
+    assert_eq!(get_node_versions(&state_merkle_db), vec![0, 0, 2, 2]);
+}
+
+fn put_stale_node_indices(state_merkle_db: &DB, versions: &[Version]) {
+    let batch = SchemaBatch::new();
+    for version in versions {
+        let index = StaleNodeIndex {
+            stale_since_version: *version,
+            node_key: NodeKey::new_empty_path(version - 1),
+        };
+        batch.put::<StaleNodeIndexSchema>(&index, &()).unwrap();
+    }
+    state_merkle_db.write_schemas(batch).unwrap();
+}
+
+fn get_stale_since_versions(state_merkle_db: &DB) -> Vec<Version> {
+    let mut iter = state_merkle_db
+        .iter::<StaleNodeIndexSchema>(ReadOptions::default())
+        .unwrap();
+    iter.seek_to_first();
+    iter.map(|item| item.unwrap().0.stale_since_version)
+        .collect()
+}
+
+fn run_delete_stale_node_index(tmp_dir: &TempPath, args: &[&str]) -> Result<()> {
+    let db_dir = tmp_dir.path().to_str().unwrap();
+    delete_stale_node_index::Cmd::try_parse_from(
+        ["delete-stale-node-index", "--db-dir", db_dir]
+            .iter()
+            .chain(args),
+    )?
+    .run()
+}
+
+#[test]
+fn test_delete_stale_node_index_above_latest_root_with_fix() {
+    let tmp_dir = TempPath::new();
+    let state_merkle_db = open_state_merkle_db(&tmp_dir);
+    put_nodes(&state_merkle_db, 2, true);
+    put_stale_node_indices(&state_merkle_db, &[1, 2, 3, 4]);
+    drop(state_merkle_db);
+
+    run_delete_stale_node_index(&tmp_dir, &["--start-version", "3"]).unwrap();
+    // Entries the pruner still needs are protected.
+    assert!(run_delete_stale_node_index(&tmp_dir, &["--start-version", "2", "--fix"]).is_err());
+    let state_merkle_db = open_state_merkle_db(&tmp_dir);
+    assert_eq!(get_stale_since_versions(&state_merkle_db), vec![1, 2, 3, 4]);
+    drop(state_merkle_db);
+
+    run_delete_stale_node_index(&tmp_dir, &["--start-version", "3", "--fix"]).unwrap();
+    let state_merkle_db = open_state_merkle_db(&tmp_dir);
+    assert_eq!(get_stale_since_versions(&state_merkle_db), vec![1, 2]);
+}
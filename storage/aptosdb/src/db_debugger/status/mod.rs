@@ -0,0 +1,98 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::{
+        get_current_version_in_ledger_db, get_current_version_in_state_merkle_db, DbDir,
+        OutputFormat,
+    },
+    schema::{epoch_by_version::EpochByVersionSchema, ledger_info::LedgerInfoSchema},
+};
+use anyhow::Result;
+use aptos_schemadb::ReadOptions;
+use aptos_types::transaction::Version;
+use clap::Parser;
+use serde::Serialize;
+
+#[derive(Parser)]
+#[clap(about = "Print the current versions of the ledger db and state merkle db.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    #[clap(long, arg_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+#[derive(Serialize)]
+struct StatusReport {
+    ledger_db_version: Option<Version>,
+    state_merkle_db_version: Option<Version>,
+    in_sync: bool,
+    latest_epoch_ending_version: Option<Version>,
+    latest_epoch_ending_epoch: Option<u64>,
+    latest_ledger_info_epoch: Option<u64>,
+}
+
+impl StatusReport {
+    fn print_text(&self) {
+        println!("Ledger db version: {:?}", self.ledger_db_version);
+        println!(
+            "State merkle db version: {:?}",
+            self.state_merkle_db_version
+        );
+        println!("In sync: {}", self.in_sync);
+        match (
+            self.latest_epoch_ending_epoch,
+            self.latest_epoch_ending_version,
+        ) {
+            (Some(epoch), Some(version)) => {
+                println!(
+                    "Latest epoch ending: epoch {} at version {}",
+                    epoch, version
+                )
+            },
+            _ => println!("Latest epoch ending: None"),
+        }
+        println!(
+            "Latest LedgerInfo epoch: {:?}",
+            self.latest_ledger_info_epoch
+        );
+    }
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let ledger_db = self.db_dir.open_ledger_db()?;
+        let state_merkle_db = self.db_dir.open_state_merkle_db()?;
+
+        let ledger_db_version = get_current_version_in_ledger_db(&ledger_db)?;
+        let state_merkle_db_version = get_current_version_in_state_merkle_db(&state_merkle_db)?;
+
+        let latest_epoch_ending = {
+            let mut iter = ledger_db.iter::<EpochByVersionSchema>(ReadOptions::default())?;
+            iter.seek_to_last();
+            iter.next().transpose()?
+        };
+        let latest_ledger_info_epoch = {
+            let mut iter = ledger_db.iter::<LedgerInfoSchema>(ReadOptions::default())?;
+            iter.seek_to_last();
+            iter.next().transpose()?.map(|(epoch, _)| epoch)
+        };
+
+        let report = StatusReport {
+            ledger_db_version,
+            state_merkle_db_version,
+            in_sync: ledger_db_version == state_merkle_db_version,
+            latest_epoch_ending_version: latest_epoch_ending.map(|(version, _)| version),
+            latest_epoch_ending_epoch: latest_epoch_ending.map(|(_, epoch)| epoch),
+            latest_ledger_info_epoch,
+        };
+        match self.format {
+            OutputFormat::Text => report.print_text(),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+
+        Ok(())
+    }
+}
@@ -163,6 +163,11 @@ impl DB {
         Ok(Self::log_construct(name, inner))
     }
 
+    /// Lists the names of the column families in the db at `path`, without opening it.
+    pub fn list_cf(opts: &rocksdb::Options, path: impl AsRef<Path>) -> Result<Vec<String>> {
+        Ok(rocksdb::DB::list_cf(opts, path)?)
+    }
+
     fn log_construct(name: &'static str, inner: rocksdb::DB) -> DB {
         info!(rocksdb_name = name, "Opened RocksDB.");
         DB { name, inner }
@@ -308,6 +308,22 @@ fn test_open_read_only() {
     }
 }
 
+#[test]
+fn test_list_cf() {
+    let tmpdir = aptos_temppath::TempPath::new();
+    {
+        let _db = open_db(&tmpdir);
+    }
+    let mut cfs = DB::list_cf(&rocksdb::Options::default(), tmpdir.path()).unwrap();
+    cfs.sort();
+    let mut expected: Vec<_> = get_column_families()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    expected.sort();
+    assert_eq!(cfs, expected);
+}
+
 #[test]
 fn test_open_as_secondary() {
     let tmpdir = aptos_temppath::TempPath::new();
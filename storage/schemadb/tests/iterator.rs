@@ -265,3 +265,45 @@ fn test_seek_for_prev_by_2prefix() {
     iter.seek_for_prev(&KeyPrefix2(2, 0)).unwrap();
     assert_eq!(collect_values(iter), [114, 112, 110, 104, 102, 100]);
 }
+
+define_schema!(RawTestSchema, Vec<u8>, Vec<u8>, "TestCF");
+
+impl KeyCodec<RawTestSchema> for Vec<u8> {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.clone())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(data.to_vec())
+    }
+}
+
+impl ValueCodec<RawTestSchema> for Vec<u8> {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.clone())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(data.to_vec())
+    }
+}
+
+#[test]
+fn test_skip_corrupt_row() {
+    let db = TestDB::new();
+    // A value too short to be decoded as a TestValue.
+    db.db
+        .put::<RawTestSchema>(&TestKey(1, 0, 1).encode_key().unwrap(), &vec![0])
+        .unwrap();
+
+    let mut iter = db.iter();
+    iter.seek_to_first();
+    assert_eq!(iter.next().unwrap().unwrap().1, TestValue(100));
+    assert!(iter.next().unwrap().is_err());
+    assert_eq!(iter.next().unwrap().unwrap().1, TestValue(102));
+
+    let mut iter = db.rev_iter();
+    iter.seek(&TestKey(1, 0, 2)).unwrap();
+    let values: Vec<_> = iter.filter_map(|row| row.ok()).map(|(_, v)| v.0).collect();
+    assert_eq!(values, [102, 100]);
+}
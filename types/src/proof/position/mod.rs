@@ -532,3 +532,13 @@ pub fn postorder_to_inorder(mut node: u64) -> u64 {
     let pos = bitmap >> level;
     Position::from_level_and_pos(level, pos).to_inorder_index()
 }
+
+/// Returns the number of frozen nodes in an accumulator with `num_leaves` leaves, which is also
+/// the postorder index the next frozen node will be stored at.
+///
+/// The leaves form one perfect subtree per bit set in `num_leaves`, and a perfect subtree with
+/// `2^k` leaves has `2^(k+1) - 1` nodes. Summing over the subtrees gives
+/// `2 * num_leaves - num_leaves.count_ones()`.
+pub fn num_frozen_nodes_in_accumulator(num_leaves: u64) -> u64 {
+    (num_leaves << 1) - u64::from(num_leaves.count_ones())
+}
@@ -765,3 +765,32 @@ fn test_left_most_child() {
         Position::from_inorder_index(14)
     );
 }
+
+#[test]
+fn test_num_frozen_nodes_in_accumulator() {
+    assert_eq!(num_frozen_nodes_in_accumulator(0), 0);
+    assert_eq!(num_frozen_nodes_in_accumulator(1), 1);
+    assert_eq!(num_frozen_nodes_in_accumulator(2), 3);
+    assert_eq!(num_frozen_nodes_in_accumulator(3), 4);
+    assert_eq!(num_frozen_nodes_in_accumulator(4), 7);
+    assert_eq!(num_frozen_nodes_in_accumulator(5), 8);
+    assert_eq!(num_frozen_nodes_in_accumulator(7), 11);
+    assert_eq!(num_frozen_nodes_in_accumulator(8), 15);
+    assert_eq!(num_frozen_nodes_in_accumulator(1 << 32), (1 << 33) - 1);
+}
+
+#[test]
+fn test_num_frozen_nodes_in_accumulator_matches_last_frozen_node() {
+    for num_leaves in 1..1000u64 {
+        // Appending the last leaf freezes it and every ancestor it is the right most leaf of,
+        // the highest of which is the last frozen node in postorder.
+        let mut position = Position::from_leaf_index(num_leaves - 1);
+        while position.is_right_child() {
+            position = position.parent();
+        }
+        assert_eq!(
+            num_frozen_nodes_in_accumulator(num_leaves),
+            position.to_postorder_index() + 1,
+        );
+    }
+}